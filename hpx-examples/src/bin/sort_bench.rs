@@ -0,0 +1,52 @@
+use hpx_sys::ParallelSort;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+const SIZES: [usize; 4] = [1_000, 100_000, 1_000_000, 10_000_000];
+const RUNS: u32 = 5;
+
+// Best of `RUNS` timings of `sort` on fresh copies of `data`, so one slow run
+// (page faults, thread startup) does not skew the result.
+fn best_of(data: &[u64], sort: impl Fn(&mut [u64])) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let mut copy = data.to_vec();
+            let start = Instant::now();
+            sort(&mut copy);
+            let elapsed = start.elapsed();
+            assert!(copy.windows(2).all(|w| w[0] <= w[1]));
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+// The comparator versions are timed on purpose: they are what the `par_`
+// functions have to beat.
+#[allow(clippy::unnecessary_sort_by)]
+fn hpx_main(_: Vec<String>) -> i32 {
+    println!(
+        "{:>10} {:>14} {:>14} {:>14} {:>14}",
+        "len", "sort_by", "par_sort_by", "unstable_by", "par_unstable"
+    );
+    for len in SIZES {
+        let data: Vec<u64> = (0..len).map(|_| rand::random()).collect();
+        let sort_by = best_of(&data, |v| v.sort_by(|a, b| a.cmp(b)));
+        // Safety: `u64::cmp` is a total order.
+        let par_sort_by = best_of(&data, |v| unsafe { v.par_sort_by(|a, b| a.cmp(b)) });
+        let unstable_by = best_of(&data, |v| v.sort_unstable_by(|a, b| a.cmp(b)));
+        let par_unstable = best_of(&data, |v| unsafe {
+            v.par_sort_unstable_by(|a, b| a.cmp(b))
+        });
+        println!(
+            "{:>10} {:>14?} {:>14?} {:>14?} {:>14?}",
+            len, sort_by, par_sort_by, unstable_by, par_unstable
+        );
+    }
+    hpx_sys::ffi::finalize()
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+    process::exit(hpx_sys::init(hpx_main, args));
+}
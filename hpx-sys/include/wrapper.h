@@ -1,9 +1,31 @@
 #pragma once
 
 #include <hpx/hpx_init.hpp>
+#include <hpx/async.hpp>
+#include <hpx/execution.hpp>
+#include <hpx/executors/limiting_executor.hpp>
+#include <hpx/future.hpp>
+#include <hpx/runtime.hpp>
+#include <hpx/stop_token.hpp>
+#include <hpx/thread.hpp>
 #include <hpx/algorithm.hpp>
+#include <hpx/numeric.hpp>
+#include <hpx/include/performance_counters.hpp>
+#include <hpx/include/resource_partitioner.hpp>
+#include <hpx/iterator_support/counting_iterator.hpp>
+#include <hpx/modules/topology.hpp>
+#include <hpx/iterator_support/counting_shape.hpp>
+#include <hpx/parallel/algorithms/reduce_by_key.hpp>
+#include <hpx/parallel/algorithms/sort_by_key.hpp>
 #include <iostream>
+#include <algorithm>
 #include <cstdint>
+#include <functional>
+#include <iterator>
+#include <memory>
+#include <stdexcept>
+#include <string>
+#include <type_traits>
 #include <vector>
 
 #include "rust/cxx.h"
@@ -16,6 +38,39 @@ inline std::int32_t init(rust::Fn<int(int, char **)> rust_fn, int argc, char **a
         argc, argv);
 }
 
+// `PoolSpec` is a shared struct defined after this header, so the function is
+// a template.
+template <typename Pool>
+inline std::int32_t init_with_pools(rust::Fn<int(int, char **)> rust_fn, int argc, char **argv,
+                                    rust::Slice<const Pool> pools) {
+    hpx::init_params params;
+    params.rp_callback = [pools](hpx::resource::partitioner& rp,
+                                 hpx::program_options::variables_map const&) {
+        for (const auto& pool : pools) {
+            rp.create_thread_pool(
+                std::string(pool.name), static_cast<hpx::resource::scheduling_policy>(pool.scheduler),
+                static_cast<hpx::threads::policies::scheduler_mode>(pool.mode));
+        }
+        // The PU sets are resolved and checked to be disjoint on the Rust side.
+        for (const auto& domain : rp.numa_domains()) {
+            for (const auto& core : domain.cores()) {
+                for (const auto& pu : core.pus()) {
+                    for (const auto& pool : pools) {
+                        if (std::find(pool.pus.begin(), pool.pus.end(), pu.id()) != pool.pus.end()) {
+                            rp.add_resource(pu, std::string(pool.name));
+                        }
+                    }
+                }
+            }
+        }
+    };
+    return hpx::init(
+        [&](int argc, char **argv) {
+            return rust_fn(argc, argv);
+        },
+        argc, argv, params);
+}
+
 inline std::int32_t finalize_with_timeout(double shutdown_timeout, double localwait) {
 	return hpx::finalize(shutdown_timeout, localwait);
 }
@@ -34,45 +89,408 @@ inline std::int32_t disconnect_with_timeout(double shutdown_timeout, double loca
 
 inline std::int32_t finalize() { return hpx::finalize(); }
 
-inline void hpx_copy(rust::Slice<const int32_t> src, rust::Slice<int32_t> dest) {
-    hpx::copy(hpx::execution::par, src.begin(), src.end(), dest.begin());
+inline void hpx_this_thread_yield() { hpx::this_thread::yield(); }
+
+inline void hpx_this_thread_sleep_for(std::uint64_t nanoseconds) {
+    hpx::this_thread::sleep_for(std::chrono::nanoseconds(nanoseconds));
 }
 
-inline void hpx_copy_n(rust::Slice<const int32_t> src, size_t count, rust::Slice<int32_t> dest) {
-    hpx::copy_n(hpx::execution::par, src.begin(), count, dest.begin());
+// The address behind the thread id, which is null outside of HPX threads.
+inline std::uint64_t hpx_this_thread_get_id() {
+    return reinterpret_cast<std::uintptr_t>(hpx::this_thread::get_id().native_handle().get());
 }
 
-inline void hpx_copy_if(const rust::Vec<int32_t>& src, rust::Vec<int32_t>& dest, 
-                        rust::Fn<bool(int32_t)> pred) {
-    std::vector<int32_t> cpp_dest(src.size());
+inline std::size_t hpx_get_worker_thread_num() { return hpx::get_worker_thread_num(); }
 
-    auto result = hpx::copy_if(hpx::execution::par, 
-                 src.begin(), src.end(), 
-                 cpp_dest.begin(),
-                 [&](int32_t value) { return pred(value); });
+inline std::uint32_t hpx_get_num_worker_threads() { return hpx::get_num_worker_threads(); }
 
-    cpp_dest.resize(std::distance(cpp_dest.begin(), result));
+inline std::uint32_t hpx_get_locality_id() { return hpx::get_locality_id(); }
 
-    dest.clear();
-    dest.reserve(cpp_dest.size());
-    for (const auto& item : cpp_dest) {
-        dest.push_back(item);
+// `Topology` is a shared struct defined after this header, so the function is
+// a template. `hpx::threads::create_topology` does not need the runtime.
+template <typename Topology>
+inline void hpx_get_topology(Topology& topology) {
+    const auto& topo = hpx::threads::create_topology();
+    topology.sockets = topo.get_number_of_sockets();
+    topology.numa_nodes = topo.get_number_of_numa_nodes();
+    topology.cores = topo.get_number_of_cores();
+    topology.pus = topo.get_number_of_pus();
+    for (std::size_t numa = 0; numa != topology.numa_nodes; ++numa) {
+        topology.numa_node_cores.push_back(topo.get_number_of_numa_node_cores(numa));
+        topology.numa_node_pus.push_back(topo.get_number_of_numa_node_pus(numa));
+    }
+    std::vector<std::size_t> pu_cores(topology.pus);
+    for (std::size_t core = 0; core != topology.cores; ++core) {
+        auto mask = topo.get_core_affinity_mask(core);
+        for (std::size_t pu = 0; pu != topology.pus && pu != hpx::threads::mask_size(mask); ++pu) {
+            if (hpx::threads::test(mask, pu)) {
+                pu_cores[pu] = core;
+            }
+        }
+    }
+    for (auto core : pu_cores) {
+        topology.pu_cores.push_back(core);
     }
+    // Queried on a single core: on the machine mask hwloc looks for a cache
+    // covering every PU, which usually only exists for the last level.
+    auto core = topo.get_core_affinity_mask(0);
+    topology.l1_cache_size = topo.get_cache_size(core, 1);
+    topology.l2_cache_size = topo.get_cache_size(core, 2);
+    topology.l3_cache_size = topo.get_cache_size(core, 3);
 }
 
-inline std::int64_t hpx_count(const rust::Vec<int32_t>& vec, int32_t value) {
-    return hpx::count(hpx::execution::par, vec.begin(), vec.end(), value);
+inline rust::Vec<std::size_t> hpx_get_cpubind_pus() {
+    auto mask = hpx::threads::create_topology().get_cpubind_mask();
+    rust::Vec<std::size_t> pus;
+    for (std::size_t pu = 0; pu != hpx::threads::mask_size(mask); ++pu) {
+        if (hpx::threads::test(mask, pu)) {
+            pus.push_back(pu);
+        }
+    }
+    return pus;
 }
 
+using hpx_performance_counter = hpx::performance_counters::performance_counter;
 
-inline int64_t hpx_count_if(const rust::Vec<int32_t>& vec, rust::Fn<bool(int32_t)> pred) {
-    std::vector<int32_t> cpp_vec(vec.begin(), vec.end());
-    
+// `CounterInfo` and `CounterValue` are shared structs defined after this
+// header, so the functions using them are templates.
+template <typename Info>
+inline void hpx_discover_counter_types(rust::Vec<Info>& infos) {
+    hpx::performance_counters::discover_counter_types(
+        [&](hpx::performance_counters::counter_info const& info, hpx::error_code&) {
+            infos.push_back(Info{rust::String(info.fullname_), rust::String(info.helptext_),
+                                 rust::String(info.unit_of_measure_)});
+            return true;
+        });
+}
+
+inline std::unique_ptr<hpx_performance_counter> hpx_performance_counter_new(rust::Str name) {
+    auto counter = std::make_unique<hpx_performance_counter>(std::string(name));
+    // The constructor resolves the name asynchronously; wait for it here so
+    // that an unknown counter throws now instead of on the first read.
+    counter->get_info(hpx::launch::sync);
+    return counter;
+}
+
+// `performance_counter` is a client holding the id of the counter, so a copy
+// refers to the same counter and the Rust side needs no mutable reference.
+template <typename Info>
+inline void hpx_performance_counter_get_info(const hpx_performance_counter& counter, Info& info) {
+    auto counter_info = hpx_performance_counter(counter).get_info(hpx::launch::sync);
+    info.name = rust::String(counter_info.fullname_);
+    info.help_text = rust::String(counter_info.helptext_);
+    info.unit_of_measure = rust::String(counter_info.unit_of_measure_);
+}
+
+template <typename Value>
+inline void hpx_performance_counter_get_value(const hpx_performance_counter& counter, bool reset,
+                                              Value& value) {
+    auto counter_value = hpx_performance_counter(counter).get_counter_value(hpx::launch::sync, reset);
+    value.time = counter_value.time_;
+    value.count = counter_value.count_;
+    value.value = counter_value.value_;
+    value.scaling = counter_value.scaling_;
+    value.scale_inverse = counter_value.scale_inverse_;
+    value.status = static_cast<std::int32_t>(counter_value.status_);
+}
+
+inline bool hpx_performance_counter_start(const hpx_performance_counter& counter) {
+    return hpx_performance_counter(counter).start(hpx::launch::sync);
+}
+
+inline bool hpx_performance_counter_stop(const hpx_performance_counter& counter) {
+    return hpx_performance_counter(counter).stop(hpx::launch::sync);
+}
+
+inline void hpx_performance_counter_reset(const hpx_performance_counter& counter) {
+    hpx_performance_counter(counter).reset(hpx::launch::sync);
+}
+
+// Before the runtime runs, the counter type is installed by a startup function,
+// so that counters named on the command line can already use it.
+template <typename Callback>
+inline void hpx_install_counter_type(rust::Str name, rust::Str help_text, rust::Str unit_of_measure,
+                                     rust::Box<Callback> callback) {
+    auto shared = std::make_shared<rust::Box<Callback>>(std::move(callback));
+    auto install = [name = std::string(name), help_text = std::string(help_text),
+                    unit_of_measure = std::string(unit_of_measure), shared]() {
+        hpx::performance_counters::install_counter_type(
+            name, [shared](bool reset) -> std::int64_t { return (*shared)->call(reset); },
+            help_text, unit_of_measure);
+    };
+    if (hpx::is_running()) {
+        install();
+    } else {
+        hpx::register_startup_function(std::move(install));
+    }
+}
+
+inline bool hpx_pool_exists(rust::Str pool) { return hpx::resource::pool_exists(std::string(pool)); }
+
+inline std::size_t hpx_get_num_thread_pools() { return hpx::resource::get_num_thread_pools(); }
+
+inline std::size_t hpx_get_pool_num_threads(rust::Str pool) {
+    return hpx::resource::get_num_threads(std::string(pool));
+}
+
+inline std::int8_t hpx_get_pool_scheduling_policy(rust::Str pool) {
+    return static_cast<std::int8_t>(
+        hpx::resource::get_partitioner().which_scheduler(std::string(pool)));
+}
+
+inline std::uint32_t hpx_get_pool_scheduler_mode(rust::Str pool) {
+    return hpx::resource::get_thread_pool(std::string(pool)).get_scheduler()->get_scheduler_mode();
+}
+
+inline void hpx_add_pool_scheduler_mode(rust::Str pool, std::uint32_t mode) {
+    hpx::resource::get_thread_pool(std::string(pool)).get_scheduler()->add_scheduler_mode(
+        static_cast<hpx::threads::policies::scheduler_mode>(mode));
+}
+
+inline void hpx_remove_pool_scheduler_mode(rust::Str pool, std::uint32_t mode) {
+    hpx::resource::get_thread_pool(std::string(pool)).get_scheduler()->remove_scheduler_mode(
+        static_cast<hpx::threads::policies::scheduler_mode>(mode));
+}
+
+// `ThreadTask` is an opaque Rust type, see the comment above `hpx_reduce_by_key_indices`.
+template <typename Task>
+inline void hpx_post(rust::Box<Task> task) {
+    hpx::post([task = std::move(task)]() mutable { task->run(); });
+}
+
+template <typename Task>
+inline std::unique_ptr<hpx::thread> hpx_thread_spawn(rust::Box<Task> task) {
+    return std::make_unique<hpx::thread>([task = std::move(task)]() mutable { task->run(); });
+}
+
+inline void hpx_thread_join(hpx::thread& thread) { thread.join(); }
+
+inline void hpx_thread_detach(hpx::thread& thread) { thread.detach(); }
+
+inline std::uint64_t hpx_thread_get_id(const hpx::thread& thread) {
+    return reinterpret_cast<std::uintptr_t>(thread.get_id().native_handle().get());
+}
+
+template <typename Task>
+inline std::unique_ptr<hpx::jthread> hpx_jthread_spawn(rust::Box<Task> task) {
+    return std::make_unique<hpx::jthread>([task = std::move(task)](hpx::stop_token token) mutable {
+        task->run_with_stop_token(std::make_unique<hpx::stop_token>(std::move(token)));
+    });
+}
+
+inline void hpx_jthread_join(hpx::jthread& thread) { thread.join(); }
+
+inline bool hpx_jthread_request_stop(hpx::jthread& thread) { return thread.request_stop(); }
+
+inline std::uint64_t hpx_jthread_get_id(const hpx::jthread& thread) {
+    return reinterpret_cast<std::uintptr_t>(thread.get_id().native_handle().get());
+}
+
+inline bool hpx_stop_token_stop_requested(const hpx::stop_token& token) {
+    return token.stop_requested();
+}
+
+inline bool hpx_stop_token_stop_possible(const hpx::stop_token& token) {
+    return token.stop_possible();
+}
+
+inline std::unique_ptr<hpx::stop_token> hpx_stop_token_clone(const hpx::stop_token& token) {
+    return std::make_unique<hpx::stop_token>(token);
+}
+
+inline std::unique_ptr<hpx::stop_source> hpx_stop_source_new() {
+    return std::make_unique<hpx::stop_source>();
+}
+
+inline std::unique_ptr<hpx::stop_source> hpx_stop_source_clone(const hpx::stop_source& source) {
+    return std::make_unique<hpx::stop_source>(source);
+}
+
+inline std::unique_ptr<hpx::stop_token> hpx_stop_source_get_token(const hpx::stop_source& source) {
+    return std::make_unique<hpx::stop_token>(source.get_token());
+}
+
+// A copy shares the stop state, so requesting through it does not need a
+// mutable reference from Rust.
+inline bool hpx_stop_source_request_stop(const hpx::stop_source& source) {
+    return hpx::stop_source(source).request_stop();
+}
+
+inline bool hpx_stop_source_stop_requested(const hpx::stop_source& source) {
+    return source.stop_requested();
+}
+
+inline bool hpx_stop_source_stop_possible(const hpx::stop_source& source) {
+    return source.stop_possible();
+}
+
+// `hpx::stop_callback` needs a copyable callback type, so the Rust task is
+// shared between the copies of the `std::function`.
+using hpx_stop_callback = hpx::stop_callback<std::function<void()>>;
+
+template <typename Task>
+inline std::unique_ptr<hpx_stop_callback> hpx_stop_callback_new(const hpx::stop_token& token,
+                                                                rust::Box<Task> task) {
+    auto shared = std::make_shared<rust::Box<Task>>(std::move(task));
+    return std::make_unique<hpx_stop_callback>(token,
+                                               std::function<void()>([shared] { (*shared)->run(); }));
+}
+
+using hpx_future = hpx::future<void>;
+
+// Executors have unrelated types, so Rust sees them through this interface.
+class hpx_executor {
+public:
+    virtual ~hpx_executor() = default;
+    virtual hpx_future async_execute(std::function<void()> f) const = 0;
+    virtual hpx_future bulk_async_execute(std::function<void(std::size_t)> f,
+                                          std::size_t shape) const = 0;
+    virtual void for_each_index(std::size_t len,
+                                const std::function<void(std::size_t)>& f) const = 0;
+};
+
+// Executors differ in what `bulk_async_execute` returns.
+inline hpx_future hpx_to_future(hpx_future f) { return f; }
+
+inline hpx_future hpx_to_future(std::vector<hpx_future> fs) {
+    return hpx::when_all(std::move(fs)).then([](auto&&) {});
+}
+
+template <typename Executor>
+class hpx_executor_impl final : public hpx_executor {
+public:
+    template <typename... Ts>
+    explicit hpx_executor_impl(Ts&&... ts) : exec_(std::forward<Ts>(ts)...) {}
+
+    hpx_future async_execute(std::function<void()> f) const override {
+        return hpx::parallel::execution::async_execute(exec_, std::move(f));
+    }
+
+    hpx_future bulk_async_execute(std::function<void(std::size_t)> f,
+                                  std::size_t shape) const override {
+        return hpx_to_future(hpx::parallel::execution::bulk_async_execute(
+            exec_, std::move(f), hpx::util::counting_shape(shape)));
+    }
+
+    void for_each_index(std::size_t len,
+                        const std::function<void(std::size_t)>& f) const override {
+        hpx::util::counting_iterator<std::size_t> first(0);
+        hpx::for_each(hpx::execution::par.on(exec_), first, first + len, f);
+    }
+
+private:
+    // Some executors, like `limiting_executor`, count the work in flight.
+    mutable Executor exec_;
+};
+
+// The arguments are the values of the `hpx::threads` enums. An empty `pool`
+// keeps the pool of the calling thread.
+inline std::unique_ptr<hpx_executor> hpx_parallel_executor_new(rust::Str pool,
+                                                               std::int8_t priority,
+                                                               std::int8_t stacksize,
+                                                               std::int8_t hint_mode,
+                                                               std::int16_t hint) {
+    using executor = hpx_executor_impl<hpx::execution::parallel_executor>;
+    auto thread_priority = static_cast<hpx::threads::thread_priority>(priority);
+    auto thread_stacksize = static_cast<hpx::threads::thread_stacksize>(stacksize);
+    hpx::threads::thread_schedule_hint schedule_hint(
+        static_cast<hpx::threads::thread_schedule_hint_mode>(hint_mode), hint);
+    if (pool.empty()) {
+        return std::make_unique<executor>(thread_priority, thread_stacksize, schedule_hint);
+    }
+    return std::make_unique<executor>(&hpx::resource::get_thread_pool(std::string(pool)),
+                                      thread_priority, thread_stacksize, schedule_hint);
+}
+
+inline std::unique_ptr<hpx_executor> hpx_sequenced_executor_new() {
+    return std::make_unique<hpx_executor_impl<hpx::execution::sequenced_executor>>();
+}
+
+inline std::unique_ptr<hpx_executor> hpx_fork_join_executor_new() {
+    return std::make_unique<hpx_executor_impl<hpx::execution::experimental::fork_join_executor>>();
+}
+
+inline std::unique_ptr<hpx_executor> hpx_scheduler_executor_new() {
+    using scheduler = hpx::execution::experimental::thread_pool_scheduler;
+    return std::make_unique<
+        hpx_executor_impl<hpx::execution::experimental::scheduler_executor<scheduler>>>(scheduler{});
+}
+
+inline std::unique_ptr<hpx_executor> hpx_limiting_executor_new(std::size_t lower, std::size_t upper) {
+    using limiting = hpx::execution::experimental::limiting_executor<hpx::execution::parallel_executor>;
+    return std::make_unique<hpx_executor_impl<limiting>>(hpx::execution::parallel_executor{}, lower,
+                                                         upper);
+}
+
+template <typename Task>
+inline std::unique_ptr<hpx_future> hpx_executor_async_execute(const hpx_executor& exec,
+                                                              rust::Box<Task> task) {
+    auto shared = std::make_shared<rust::Box<Task>>(std::move(task));
+    return std::make_unique<hpx_future>(exec.async_execute([shared] { (*shared)->run(); }));
+}
+
+template <typename Task>
+inline std::unique_ptr<hpx_future> hpx_executor_bulk_async_execute(const hpx_executor& exec,
+                                                                   std::size_t shape,
+                                                                   rust::Box<Task> task) {
+    auto shared = std::make_shared<rust::Box<Task>>(std::move(task));
+    return std::make_unique<hpx_future>(
+        exec.bulk_async_execute([shared](std::size_t i) { (*shared)->call(i); }, shape));
+}
+
+template <typename Function>
+inline void hpx_executor_for_each_index(const hpx_executor& exec, std::size_t len,
+                                        const Function& f) {
+    exec.for_each_index(len, [&](std::size_t i) { f.call(i); });
+}
+
+inline void hpx_future_wait(const hpx_future& future) { future.wait(); }
+
+inline bool hpx_future_is_ready(const hpx_future& future) { return future.is_ready(); }
+
+// Destination slices come from Rust with any length, so the bindings writing
+// through one check it first. The exception reaches Rust as an `Err`.
+inline void hpx_check_dest(std::size_t dest_size, std::size_t len) {
+    if (dest_size < len) {
+        throw std::length_error("destination slice is too short");
+    }
+}
+
+inline void hpx_check_position(std::size_t pos, std::size_t size) {
+    if (pos > size) {
+        throw std::out_of_range("position past the end of the slice");
+    }
+}
+
+inline void hpx_copy(rust::Slice<const int32_t> src, rust::Slice<int32_t> dest) {
+    hpx::copy(hpx::execution::par, src.begin(), src.end(), dest.begin());
+}
+
+inline void hpx_copy_n(rust::Slice<const int32_t> src, size_t count, rust::Slice<int32_t> dest) {
+    hpx::copy_n(hpx::execution::par, src.begin(), count, dest.begin());
+}
+
+inline std::size_t hpx_copy_if(rust::Slice<const int32_t> src, rust::Slice<int32_t> dest,
+                               rust::Fn<bool(int32_t)> pred) {
+    hpx_check_dest(dest.size(), src.size());
+    auto result = hpx::copy_if(hpx::execution::par,
+                               src.begin(), src.end(),
+                               dest.begin(),
+                               [&](int32_t value) { return pred(value); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+inline std::int64_t hpx_count(rust::Slice<const int32_t> src, int32_t value) {
+    return hpx::count(hpx::execution::par, src.begin(), src.end(), value);
+}
+
+inline int64_t hpx_count_if(rust::Slice<const int32_t> src, rust::Fn<bool(int32_t)> pred) {
     auto result = hpx::count_if(hpx::execution::par,
-                                cpp_vec.begin(),
-                                cpp_vec.end(),
+                                src.begin(),
+                                src.end(),
                                 [&](int32_t value) { return pred(value); });
-    
+
     return static_cast<int64_t>(result);
 }
 
@@ -96,6 +514,84 @@ inline void hpx_fill(rust::Slice<int32_t> src, int32_t value) {
     hpx::fill(hpx::execution::par, src.begin(), src.end(), value);
 }
 
+template <typename T>
+inline void hpx_iota(rust::Slice<T> dest, T value) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    hpx::transform(hpx::execution::par, first, first + dest.size(), dest.begin(),
+                   [&](std::size_t i) {
+                       // Add in the unsigned type so integers wrap around like Rust's
+                       // `wrapping_add` instead of overflowing, which is undefined for
+                       // signed types.
+                       if constexpr (std::is_integral_v<T>) {
+                           using U = std::make_unsigned_t<T>;
+                           return static_cast<T>(static_cast<U>(value) + static_cast<U>(i));
+                       } else {
+                           return static_cast<T>(value + static_cast<T>(i));
+                       }
+                   });
+}
+
+template <typename T>
+inline void hpx_replace(rust::Slice<T> src, T old_value, T new_value) {
+    hpx::replace(hpx::execution::par, src.begin(), src.end(), old_value, new_value);
+}
+
+template <typename T>
+inline void hpx_replace_copy(rust::Slice<const T> src, rust::Slice<T> dest,
+                             T old_value, T new_value) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::replace_copy(hpx::execution::par, src.begin(), src.end(), dest.begin(),
+                      old_value, new_value);
+}
+
+template <typename T>
+inline std::size_t hpx_remove(rust::Slice<T> src, T value) {
+    auto result = hpx::remove(hpx::execution::par, src.begin(), src.end(), value);
+    return static_cast<std::size_t>(std::distance(src.begin(), result));
+}
+
+template <typename T>
+inline std::size_t hpx_unique(rust::Slice<T> src) {
+    auto result = hpx::unique(hpx::execution::par, src.begin(), src.end());
+    return static_cast<std::size_t>(std::distance(src.begin(), result));
+}
+
+template <typename T>
+inline std::size_t hpx_unique_copy(rust::Slice<const T> src, rust::Slice<T> dest) {
+    hpx_check_dest(dest.size(), src.size());
+    auto result = hpx::unique_copy(hpx::execution::par, src.begin(), src.end(), dest.begin());
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename T>
+inline void hpx_reverse(rust::Slice<T> src) {
+    hpx::reverse(hpx::execution::par, src.begin(), src.end());
+}
+
+template <typename T>
+inline void hpx_rotate(rust::Slice<T> src, std::size_t middle) {
+    hpx_check_position(middle, src.size());
+    hpx::rotate(hpx::execution::par, src.begin(), src.begin() + middle, src.end());
+}
+
+template <typename T>
+inline void hpx_rotate_copy(rust::Slice<const T> src, std::size_t middle, rust::Slice<T> dest) {
+    hpx_check_position(middle, src.size());
+    hpx_check_dest(dest.size(), src.size());
+    hpx::rotate_copy(hpx::execution::par, src.begin(), src.begin() + middle, src.end(),
+                     dest.begin());
+}
+
+template <typename T>
+inline void hpx_shift_left(rust::Slice<T> src, std::size_t n) {
+    hpx::shift_left(hpx::execution::par, src.begin(), src.end(), n);
+}
+
+template <typename T>
+inline void hpx_shift_right(rust::Slice<T> src, std::size_t n) {
+    hpx::shift_right(hpx::execution::par, src.begin(), src.end(), n);
+}
+
 inline int64_t hpx_find(rust::Slice<const int32_t> src, int32_t value) {
     auto result = hpx::find(hpx::execution::par,
                             src.begin(),
@@ -112,28 +608,22 @@ inline void hpx_sort(rust::Slice<int32_t> src) {
     hpx::sort(hpx::execution::par, src.begin(), src.end());
 }
 
-inline void hpx_sort_comp(rust::Vec<int32_t>& src, rust::Fn<bool(int32_t, int32_t)> comp) {
+inline void hpx_sort_comp(rust::Slice<int32_t> src, rust::Fn<bool(int32_t, int32_t)> comp) {
     hpx::sort(hpx::execution::par, src.begin(), src.end(),
         [&](int32_t a, int32_t b) { return comp(a, b); });
 }
 
 inline void hpx_merge(rust::Slice<const int32_t> src1, 
                       rust::Slice<const int32_t> src2, 
-                      rust::Vec<int32_t>& dest) {
-    dest.clear();
-    dest.reserve(src1.size() + src2.size());
-    
-    for (size_t i = 0; i < src1.size() + src2.size(); ++i) {
-        dest.push_back(0);
-    }
-
+                      rust::Slice<int32_t> dest) {
+    hpx_check_dest(dest.size(), src1.size() + src2.size());
     hpx::merge(hpx::execution::par,
                src1.begin(), src1.end(),
                src2.begin(), src2.end(),
                dest.begin());
 }
 
-inline void hpx_partial_sort(rust::Vec<int32_t>& src, size_t last) {
+inline void hpx_partial_sort(rust::Slice<int32_t> src, size_t last) {
     if (last > src.size()) {
         last = src.size();
     }
@@ -144,7 +634,7 @@ inline void hpx_partial_sort(rust::Vec<int32_t>& src, size_t last) {
                       src.end());
 }
 
-inline void hpx_partial_sort_comp(rust::Vec<int32_t>& src, size_t last, 
+inline void hpx_partial_sort_comp(rust::Slice<int32_t> src, size_t last, 
                                   rust::Fn<bool(int32_t, int32_t)> comp) {
     if (last > src.size()) {
         last = src.size();
@@ -156,3 +646,553 @@ inline void hpx_partial_sort_comp(rust::Vec<int32_t>& src, size_t last,
                       src.end(),
                       [&](int32_t a, int32_t b) { return comp(a, b); });
 }
+
+template <typename T, typename Op>
+inline void hpx_inclusive_scan(rust::Slice<const T> src, rust::Slice<T> dest,
+                               const Op& op) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::inclusive_scan(hpx::execution::par, src.begin(), src.end(), dest.begin(),
+                        [&](T a, T b) { return op.call(a, b); });
+}
+
+template <typename T, typename Op>
+inline void hpx_inclusive_scan_inplace(rust::Slice<T> src, const Op& op) {
+    hpx::inclusive_scan(hpx::execution::par, src.begin(), src.end(), src.begin(),
+                        [&](T a, T b) { return op.call(a, b); });
+}
+
+template <typename T, typename Op>
+inline void hpx_exclusive_scan(rust::Slice<const T> src, rust::Slice<T> dest, T init,
+                               const Op& op) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::exclusive_scan(hpx::execution::par, src.begin(), src.end(), dest.begin(), init,
+                        [&](T a, T b) { return op.call(a, b); });
+}
+
+template <typename T, typename Op>
+inline void hpx_exclusive_scan_inplace(rust::Slice<T> src, T init, const Op& op) {
+    hpx::exclusive_scan(hpx::execution::par, src.begin(), src.end(), src.begin(), init,
+                        [&](T a, T b) { return op.call(a, b); });
+}
+
+template <typename T, typename Op, typename Conv>
+inline void hpx_transform_inclusive_scan(rust::Slice<const T> src, rust::Slice<T> dest,
+                                         const Op& op, const Conv& conv) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::transform_inclusive_scan(hpx::execution::par, src.begin(), src.end(), dest.begin(),
+                                  [&](T a, T b) { return op.call(a, b); },
+                                  [&](T value) { return conv.call(value); });
+}
+
+template <typename T, typename Op, typename Conv>
+inline void hpx_transform_inclusive_scan_inplace(rust::Slice<T> src, const Op& op,
+                                                 const Conv& conv) {
+    hpx::transform_inclusive_scan(hpx::execution::par, src.begin(), src.end(), src.begin(),
+                                  [&](T a, T b) { return op.call(a, b); },
+                                  [&](T value) { return conv.call(value); });
+}
+
+template <typename T, typename Op, typename Conv>
+inline void hpx_transform_exclusive_scan(rust::Slice<const T> src, rust::Slice<T> dest, T init,
+                                         const Op& op, const Conv& conv) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::transform_exclusive_scan(hpx::execution::par, src.begin(), src.end(), dest.begin(),
+                                  init,
+                                  [&](T a, T b) { return op.call(a, b); },
+                                  [&](T value) { return conv.call(value); });
+}
+
+template <typename T, typename Op, typename Conv>
+inline void hpx_transform_exclusive_scan_inplace(rust::Slice<T> src, T init,
+                                                 const Op& op, const Conv& conv) {
+    hpx::transform_exclusive_scan(hpx::execution::par, src.begin(), src.end(), src.begin(),
+                                  init,
+                                  [&](T a, T b) { return op.call(a, b); },
+                                  [&](T value) { return conv.call(value); });
+}
+
+template <typename T>
+inline void hpx_uninitialized_copy(rust::Slice<const T> src, T* dest) {
+    hpx::uninitialized_copy(hpx::execution::par, src.begin(), src.end(), dest);
+}
+
+template <typename T>
+inline void hpx_uninitialized_fill(T* dest, std::size_t len, T value) {
+    hpx::uninitialized_fill(hpx::execution::par, dest, dest + len, value);
+}
+
+template <typename T, typename Op>
+inline void hpx_adjacent_difference(rust::Slice<const T> src, rust::Slice<T> dest,
+                                    const Op& op) {
+    hpx_check_dest(dest.size(), src.size());
+    hpx::adjacent_difference(hpx::execution::par, src.begin(), src.end(), dest.begin(),
+                             [&](T a, T b) { return op.call(a, b); });
+}
+
+// Callback types such as `IndexCompare` are opaque Rust types that are only defined
+// in the generated bridge, so the functions taking them are templated on the callback
+// and instantiated there.
+//
+// The keys and values are Rust types, so HPX only sees key indices: the keys
+// output holds the first index of every run of equal keys, and the values of
+// each run are reduced on the Rust side.
+template <typename Compare>
+inline std::size_t hpx_reduce_by_key_indices(std::size_t len, rust::Slice<std::size_t> run_starts,
+                                             const Compare& eq) {
+    hpx_check_dest(run_starts.size(), len);
+    std::vector<std::size_t> run_lasts(len);
+    hpx::util::counting_iterator<std::size_t> first(0);
+
+    auto [starts_end, lasts_end] = hpx::experimental::reduce_by_key(hpx::execution::par,
+        first, first + len,
+        first,
+        run_starts.begin(),
+        run_lasts.begin(),
+        [&](std::size_t a, std::size_t b) { return eq.call(a, b); },
+        [](std::size_t a, std::size_t b) { return std::max(a, b); });
+
+    return static_cast<std::size_t>(std::distance(run_starts.begin(), starts_end));
+}
+
+template <typename Compare>
+inline void hpx_sort_by_key_indices(rust::Slice<std::size_t> keys, rust::Slice<std::size_t> values,
+                                    const Compare& comp) {
+    hpx_check_dest(values.size(), keys.size());
+    hpx::experimental::sort_by_key(hpx::execution::par,
+                                   keys.begin(), keys.end(),
+                                   values.begin(),
+                                   [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline void hpx_sort_indices(rust::Slice<std::size_t> indices, const Compare& comp) {
+    hpx::sort(hpx::execution::par, indices.begin(), indices.end(),
+        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline void hpx_stable_sort_indices(rust::Slice<std::size_t> indices, const Compare& comp) {
+    hpx::stable_sort(hpx::execution::par, indices.begin(), indices.end(),
+        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline void hpx_nth_element_indices(rust::Slice<std::size_t> indices, std::size_t nth,
+                                    const Compare& comp) {
+    hpx_check_position(nth, indices.size());
+    hpx::nth_element(hpx::execution::par,
+                     indices.begin(), indices.begin() + nth, indices.end(),
+                     [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline std::size_t hpx_partial_sort_copy_indices(std::size_t len, rust::Slice<std::size_t> dest,
+                                                 const Compare& comp) {
+    auto result = hpx::partial_sort_copy(hpx::execution::par,
+        hpx::util::counting_iterator<std::size_t>(0),
+        hpx::util::counting_iterator<std::size_t>(len),
+        dest.begin(), dest.end(),
+        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename Compare>
+inline bool hpx_is_sorted_indices(std::size_t len, const Compare& comp) {
+    return hpx::is_sorted(hpx::execution::par,
+                          hpx::util::counting_iterator<std::size_t>(0),
+                          hpx::util::counting_iterator<std::size_t>(len),
+                          [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline std::size_t hpx_is_sorted_until_indices(std::size_t len, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::is_sorted_until(hpx::execution::par,
+                                       first, first + len,
+                                       [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+// Random access output iterator for `hpx_merge_into`: assigning a source index
+// through it at position `pos` calls `place.call(pos, src)`, which moves or copies
+// the Rust element straight into its destination.
+template <typename Place>
+class index_place_iterator {
+public:
+    struct reference {
+        const Place* place;
+        std::size_t pos;
+
+        reference& operator=(std::size_t src) {
+            place->call(pos, src);
+            return *this;
+        }
+    };
+
+    using iterator_category = std::random_access_iterator_tag;
+    using value_type = std::size_t;
+    using difference_type = std::ptrdiff_t;
+    using pointer = void;
+
+    index_place_iterator() = default;
+    index_place_iterator(const Place& place, std::size_t pos) : place_(&place), pos_(pos) {}
+
+    reference operator*() const { return {place_, pos_}; }
+    reference operator[](difference_type n) const { return {place_, pos_ + n}; }
+
+    index_place_iterator& operator++() { ++pos_; return *this; }
+    index_place_iterator operator++(int) { auto it = *this; ++pos_; return it; }
+    index_place_iterator& operator--() { --pos_; return *this; }
+    index_place_iterator operator--(int) { auto it = *this; --pos_; return it; }
+    index_place_iterator& operator+=(difference_type n) { pos_ += n; return *this; }
+    index_place_iterator& operator-=(difference_type n) { pos_ -= n; return *this; }
+
+    friend index_place_iterator operator+(index_place_iterator it, difference_type n) { return it += n; }
+    friend index_place_iterator operator+(difference_type n, index_place_iterator it) { return it += n; }
+    friend index_place_iterator operator-(index_place_iterator it, difference_type n) { return it -= n; }
+    friend difference_type operator-(const index_place_iterator& a, const index_place_iterator& b) {
+        return static_cast<difference_type>(a.pos_) - static_cast<difference_type>(b.pos_);
+    }
+    friend bool operator==(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ == b.pos_; }
+    friend bool operator!=(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ != b.pos_; }
+    friend bool operator<(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ < b.pos_; }
+    friend bool operator>(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ > b.pos_; }
+    friend bool operator<=(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ <= b.pos_; }
+    friend bool operator>=(const index_place_iterator& a, const index_place_iterator& b) { return a.pos_ >= b.pos_; }
+
+private:
+    const Place* place_ = nullptr;
+    std::size_t pos_ = 0;
+};
+
+template <typename Compare, typename Place>
+inline void hpx_merge_into(std::size_t len1, std::size_t len2, const Compare& comp,
+                           const Place& place) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    hpx::merge(hpx::execution::par,
+               first, first + len1,
+               first + len1, first + len1 + len2,
+               index_place_iterator<Place>(place, 0),
+               [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Predicate>
+inline std::size_t hpx_find_if_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::find_if(hpx::execution::par, first, first + len,
+                               [&](std::size_t index) { return pred.call(index); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Predicate>
+inline std::size_t hpx_find_if_not_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::find_if_not(hpx::execution::par, first, first + len,
+                                   [&](std::size_t index) { return pred.call(index); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_search_indices(std::size_t len, std::size_t needle_len, const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::search(hpx::execution::par,
+                              first, first + len,
+                              first, first + needle_len,
+                              [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_find_end_indices(std::size_t len, std::size_t needle_len,
+                                        const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::find_end(hpx::execution::par,
+                                first, first + len,
+                                first, first + needle_len,
+                                [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_find_first_of_indices(std::size_t len, std::size_t candidates_len,
+                                             const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::find_first_of(hpx::execution::par,
+                                     first, first + len,
+                                     first, first + candidates_len,
+                                     [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_adjacent_find_indices(std::size_t len, const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::adjacent_find(hpx::execution::par, first, first + len,
+                                     [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_mismatch_indices(std::size_t len1, std::size_t len2, const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::mismatch(hpx::execution::par,
+                                first, first + len1,
+                                first, first + len2,
+                                [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+
+    // Both ranges start at index 0, so the two mismatch positions are the same.
+    return static_cast<std::size_t>(std::distance(first, result.first));
+}
+
+template <typename Compare>
+inline bool hpx_starts_with_indices(std::size_t len, std::size_t prefix_len, const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::starts_with(hpx::execution::par,
+                            first, first + len,
+                            first, first + prefix_len,
+                            [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+}
+
+template <typename Predicate>
+inline bool hpx_all_of_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::all_of(hpx::execution::par, first, first + len,
+                       [&](std::size_t index) { return pred.call(index); });
+}
+
+template <typename Predicate>
+inline bool hpx_any_of_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::any_of(hpx::execution::par, first, first + len,
+                       [&](std::size_t index) { return pred.call(index); });
+}
+
+template <typename Predicate>
+inline bool hpx_none_of_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::none_of(hpx::execution::par, first, first + len,
+                        [&](std::size_t index) { return pred.call(index); });
+}
+
+template <typename Compare>
+inline std::size_t hpx_min_element_indices(std::size_t len, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::min_element(hpx::execution::par, first, first + len,
+                                   [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_max_element_indices(std::size_t len, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::max_element(hpx::execution::par, first, first + len,
+                                   [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline void hpx_minmax_element_indices(std::size_t len, const Compare& comp,
+                                       std::size_t& min, std::size_t& max) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto [min_result, max_result] = hpx::minmax_element(hpx::execution::par, first, first + len,
+        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    min = static_cast<std::size_t>(std::distance(first, min_result));
+    max = static_cast<std::size_t>(std::distance(first, max_result));
+}
+
+template <typename Predicate>
+inline bool hpx_is_partitioned_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::is_partitioned(hpx::execution::par, first, first + len,
+                               [&](std::size_t index) { return pred.call(index); });
+}
+
+template <typename Predicate>
+inline std::size_t hpx_remove_if_indices(rust::Slice<std::size_t> indices, const Predicate& pred) {
+    auto result = hpx::remove_if(hpx::execution::par, indices.begin(), indices.end(),
+                                 [&](std::size_t index) { return pred.call(index); });
+    return static_cast<std::size_t>(std::distance(indices.begin(), result));
+}
+
+template <typename Predicate>
+inline std::size_t hpx_partition_indices(rust::Slice<std::size_t> indices, const Predicate& pred) {
+    auto result = hpx::partition(hpx::execution::par, indices.begin(), indices.end(),
+                                 [&](std::size_t index) { return pred.call(index); });
+    return static_cast<std::size_t>(std::distance(indices.begin(), result));
+}
+
+template <typename Predicate>
+inline std::size_t hpx_stable_partition_indices(rust::Slice<std::size_t> indices,
+                                                const Predicate& pred) {
+    auto result = hpx::stable_partition(hpx::execution::par, indices.begin(), indices.end(),
+                                        [&](std::size_t index) { return pred.call(index); });
+    return static_cast<std::size_t>(std::distance(indices.begin(), result));
+}
+
+template <typename Predicate>
+inline std::size_t hpx_partition_copy_indices(std::size_t len, rust::Slice<std::size_t> dest_true,
+                                              rust::Slice<std::size_t> dest_false,
+                                              const Predicate& pred) {
+    hpx_check_dest(dest_true.size(), len);
+    hpx_check_dest(dest_false.size(), len);
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto [true_end, false_end] = hpx::partition_copy(hpx::execution::par,
+                                                     first, first + len,
+                                                     dest_true.begin(), dest_false.begin(),
+                                                     [&](std::size_t index) { return pred.call(index); });
+    return static_cast<std::size_t>(std::distance(dest_true.begin(), true_end));
+}
+
+// The set operations write at most `len1 + len2` (union), `min(len1, len2)`
+// (intersection) or `len1` (difference) indices.
+template <typename Compare>
+inline std::size_t hpx_set_union_indices(std::size_t len1, std::size_t len2,
+                                         rust::Slice<std::size_t> dest, const Compare& comp) {
+    hpx_check_dest(dest.size(), len1 + len2);
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::set_union(hpx::execution::par,
+                                 first, first + len1,
+                                 first + len1, first + len1 + len2,
+                                 dest.begin(),
+                                 [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_set_intersection_indices(std::size_t len1, std::size_t len2,
+                                                rust::Slice<std::size_t> dest, const Compare& comp) {
+    hpx_check_dest(dest.size(), std::min(len1, len2));
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::set_intersection(hpx::execution::par,
+                                        first, first + len1,
+                                        first + len1, first + len1 + len2,
+                                        dest.begin(),
+                                        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_set_difference_indices(std::size_t len1, std::size_t len2,
+                                              rust::Slice<std::size_t> dest, const Compare& comp) {
+    hpx_check_dest(dest.size(), len1);
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::set_difference(hpx::execution::par,
+                                      first, first + len1,
+                                      first + len1, first + len1 + len2,
+                                      dest.begin(),
+                                      [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename Compare>
+inline std::size_t hpx_set_symmetric_difference_indices(std::size_t len1, std::size_t len2,
+                                                        rust::Slice<std::size_t> dest, const Compare& comp) {
+    hpx_check_dest(dest.size(), len1 + len2);
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::set_symmetric_difference(hpx::execution::par,
+                                                first, first + len1,
+                                                first + len1, first + len1 + len2,
+                                                dest.begin(),
+                                                [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename Compare>
+inline bool hpx_includes_indices(std::size_t len1, std::size_t len2, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::includes(hpx::execution::par,
+                         first, first + len1,
+                         first + len1, first + len1 + len2,
+                         [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Function>
+inline void hpx_for_each_index(std::size_t len, const Function& f) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    hpx::for_each(hpx::execution::par, first, first + len,
+                  [&](std::size_t i) { f.call(i); });
+}
+
+template <typename Compare>
+inline void hpx_make_heap_indices(rust::Slice<std::size_t> indices, const Compare& comp) {
+    hpx::make_heap(hpx::execution::par, indices.begin(), indices.end(),
+                   [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline bool hpx_is_heap_indices(std::size_t len, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::is_heap(hpx::execution::par, first, first + len,
+                        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Compare>
+inline std::size_t hpx_is_heap_until_indices(std::size_t len, const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::is_heap_until(hpx::execution::par, first, first + len,
+                                     [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+
+    return static_cast<std::size_t>(std::distance(first, result));
+}
+
+template <typename Compare>
+inline bool hpx_equal_indices(std::size_t len1, std::size_t len2, const Compare& eq) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::equal(hpx::execution::par, first, first + len1, first, first + len2,
+                      [&](std::size_t a, std::size_t b) { return eq.call(a, b); });
+}
+
+// The two ranges are `[first1, first1 + len1)` and `[first2, first2 + len2)` of
+// one index space, since `comp` is called with elements of either range first.
+template <typename Compare>
+inline bool hpx_lexicographical_compare_indices(std::size_t first1, std::size_t len1,
+                                                std::size_t first2, std::size_t len2,
+                                                const Compare& comp) {
+    hpx::util::counting_iterator<std::size_t> begin1(first1), begin2(first2);
+    return hpx::lexicographical_compare(hpx::execution::par,
+                                        begin1, begin1 + len1, begin2, begin2 + len2,
+                                        [&](std::size_t a, std::size_t b) { return comp.call(a, b); });
+}
+
+template <typename Predicate>
+inline std::size_t hpx_copy_if_indices(std::size_t len, rust::Slice<std::size_t> dest,
+                                       const Predicate& pred) {
+    hpx_check_dest(dest.size(), len);
+    hpx::util::counting_iterator<std::size_t> first(0);
+    auto result = hpx::copy_if(hpx::execution::par, first, first + len, dest.begin(),
+                               [&](std::size_t i) { return pred.call(i); });
+
+    return static_cast<std::size_t>(std::distance(dest.begin(), result));
+}
+
+template <typename T, typename Op, typename Transform>
+inline T hpx_transform_reduce_indices(std::size_t len, T init, const Op& op,
+                                      const Transform& conv) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return hpx::transform_reduce(hpx::execution::par, first, first + len, init,
+                                 [&](T a, T b) { return op.call(a, b); },
+                                 [&](std::size_t i) { return conv.call(i); });
+}
+
+template <typename Predicate>
+inline std::size_t hpx_count_if_indices(std::size_t len, const Predicate& pred) {
+    hpx::util::counting_iterator<std::size_t> first(0);
+    return static_cast<std::size_t>(
+        hpx::count_if(hpx::execution::par, first, first + len,
+                      [&](std::size_t i) { return pred.call(i); }));
+}
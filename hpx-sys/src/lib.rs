@@ -2,36 +2,749 @@
 #![allow(bad_style, non_camel_case_types, unused_extern_crates)]
 #![allow(dead_code, unused_imports)]
 
+// cxx forwards the `# Safety` sections of the unsafe functions below, but
+// clippy ignores doc attributes expanded from another crate's macro.
+#[allow(clippy::missing_safety_doc)]
 #[cxx::bridge]
 pub mod ffi {
+    /// A thread pool created by [`init_with_pools`], with the values of its
+    /// `hpx::resource::scheduling_policy` and
+    /// `hpx::threads::policies::scheduler_mode`, and the ids of its PUs.
+    struct PoolSpec {
+        name: String,
+        scheduler: i8,
+        mode: u32,
+        pus: Vec<usize>,
+    }
+
+    /// The machine as discovered by HPX through hwloc, see [`topology`].
+    ///
+    /// [`topology`]: crate::topology
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct Topology {
+        sockets: usize,
+        numa_nodes: usize,
+        cores: usize,
+        pus: usize,
+        /// The number of cores of each NUMA node.
+        numa_node_cores: Vec<usize>,
+        /// The number of PUs of each NUMA node.
+        numa_node_pus: Vec<usize>,
+        /// The id of the core of each PU, indexed by PU id.
+        pu_cores: Vec<usize>,
+        /// Cache sizes in bytes of the caches of one core, 0 if the machine
+        /// has no such cache.
+        l1_cache_size: usize,
+        l2_cache_size: usize,
+        l3_cache_size: usize,
+    }
+
+    /// The type of a performance counter, `counter_info` in HPX.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct CounterInfo {
+        name: String,
+        help_text: String,
+        unit_of_measure: String,
+    }
+
+    /// A value read from a performance counter, `counter_value` in HPX, with
+    /// the value of its `counter_status`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    struct CounterValue {
+        time: u64,
+        count: u64,
+        value: i64,
+        scaling: i64,
+        scale_inverse: bool,
+        status: i32,
+    }
+
+    extern "Rust" {
+        type IndexCompare<'a>;
+        fn call(self: &IndexCompare, a: usize, b: usize) -> bool;
+
+        type IndexPredicate<'a>;
+        fn call(self: &IndexPredicate, index: usize) -> bool;
+
+        type IndexFunction<'a>;
+        fn call(self: &IndexFunction, index: usize);
+
+        type IndexPlace<'a>;
+        fn call(self: &IndexPlace, dest: usize, src: usize);
+
+        type IndexValue_i32<'a>;
+        fn call(self: &IndexValue_i32, index: usize) -> i32;
+        type IndexValue_i64<'a>;
+        fn call(self: &IndexValue_i64, index: usize) -> i64;
+        type IndexValue_u64<'a>;
+        fn call(self: &IndexValue_u64, index: usize) -> u64;
+        type IndexValue_f64<'a>;
+        fn call(self: &IndexValue_f64, index: usize) -> f64;
+
+        type ValueOp_i32<'a>;
+        fn call(self: &ValueOp_i32, a: i32, b: i32) -> i32;
+        type ValueOp_i64<'a>;
+        fn call(self: &ValueOp_i64, a: i64, b: i64) -> i64;
+        type ValueOp_u64<'a>;
+        fn call(self: &ValueOp_u64, a: u64, b: u64) -> u64;
+        type ValueOp_f64<'a>;
+        fn call(self: &ValueOp_f64, a: f64, b: f64) -> f64;
+
+        type ValueConv_i32<'a>;
+        fn call(self: &ValueConv_i32, value: i32) -> i32;
+        type ValueConv_i64<'a>;
+        fn call(self: &ValueConv_i64, value: i64) -> i64;
+        type ValueConv_u64<'a>;
+        fn call(self: &ValueConv_u64, value: u64) -> u64;
+        type ValueConv_f64<'a>;
+        fn call(self: &ValueConv_f64, value: f64) -> f64;
+
+        type ThreadTask;
+        fn run(self: &mut ThreadTask);
+        fn run_with_stop_token(self: &mut ThreadTask, token: UniquePtr<stop_token>);
+
+        type BulkTask;
+        fn call(self: &BulkTask, index: usize);
+
+        type CounterCallback;
+        fn call(self: &CounterCallback, reset: bool) -> i64;
+    }
+
     unsafe extern "C++" {
         include!("hpx-sys/include/wrapper.h");
 
+        /// Starts the runtime, runs `func` on an HPX thread and returns its
+        /// result once the runtime has stopped.
+        ///
+        /// # Safety
+        ///
+        /// `argv` must point to `argc` valid, NUL terminated C strings that
+        /// outlive the runtime.
         unsafe fn init(
             func: unsafe fn(i32, *mut *mut c_char) -> i32,
             argc: i32,
             argv: *mut *mut c_char,
         ) -> i32;
 
+        /// Like `init`, first creating the thread pools described by `pools`.
+        ///
+        /// # Safety
+        ///
+        /// `argv` must point to `argc` valid, NUL terminated C strings that
+        /// outlive the runtime, and the PUs of `pools` must exist on this
+        /// machine.
+        unsafe fn init_with_pools(
+            func: unsafe fn(i32, *mut *mut c_char) -> i32,
+            argc: i32,
+            argv: *mut *mut c_char,
+            pools: &[PoolSpec],
+        ) -> Result<i32>;
+
         fn finalize() -> i32;
         fn finalize_with_timeout(shutdown_timeout: f64, localwait: f64) -> i32;
         fn terminate();
         fn disconnect() -> i32;
         fn disconnect_with_timeout(shutdown_timeout: f64, localwait: f64) -> i32;
+        fn hpx_this_thread_yield();
+        fn hpx_this_thread_sleep_for(nanoseconds: u64);
+        fn hpx_this_thread_get_id() -> u64;
+        fn hpx_get_worker_thread_num() -> usize;
+        fn hpx_get_num_worker_threads() -> u32;
+        fn hpx_get_locality_id() -> u32;
+        fn hpx_get_topology(topology: &mut Topology);
+        fn hpx_get_cpubind_pus() -> Vec<usize>;
+        type hpx_performance_counter;
+
+        fn hpx_discover_counter_types(infos: &mut Vec<CounterInfo>);
+        fn hpx_performance_counter_new(name: &str) -> Result<UniquePtr<hpx_performance_counter>>;
+        fn hpx_performance_counter_get_info(
+            counter: &hpx_performance_counter,
+            info: &mut CounterInfo,
+        ) -> Result<()>;
+        fn hpx_performance_counter_get_value(
+            counter: &hpx_performance_counter,
+            reset: bool,
+            value: &mut CounterValue,
+        ) -> Result<()>;
+        fn hpx_performance_counter_start(counter: &hpx_performance_counter) -> Result<bool>;
+        fn hpx_performance_counter_stop(counter: &hpx_performance_counter) -> Result<bool>;
+        fn hpx_performance_counter_reset(counter: &hpx_performance_counter) -> Result<()>;
+        fn hpx_install_counter_type(
+            name: &str,
+            help_text: &str,
+            unit_of_measure: &str,
+            callback: Box<CounterCallback>,
+        ) -> Result<()>;
+
+        fn hpx_pool_exists(pool: &str) -> bool;
+        fn hpx_get_num_thread_pools() -> usize;
+        fn hpx_get_pool_num_threads(pool: &str) -> Result<usize>;
+        fn hpx_get_pool_scheduling_policy(pool: &str) -> Result<i8>;
+        fn hpx_get_pool_scheduler_mode(pool: &str) -> Result<u32>;
+        fn hpx_add_pool_scheduler_mode(pool: &str, mode: u32) -> Result<()>;
+        fn hpx_remove_pool_scheduler_mode(pool: &str, mode: u32) -> Result<()>;
+
+        #[namespace = "hpx"]
+        type thread;
+        #[namespace = "hpx"]
+        type jthread;
+        #[namespace = "hpx"]
+        type stop_token;
+        #[namespace = "hpx"]
+        type stop_source;
+        type hpx_stop_callback;
+
+        fn hpx_post(task: Box<ThreadTask>);
+        fn hpx_thread_spawn(task: Box<ThreadTask>) -> UniquePtr<thread>;
+        fn hpx_thread_join(thread: Pin<&mut thread>);
+        fn hpx_thread_detach(thread: Pin<&mut thread>);
+        fn hpx_thread_get_id(thread: &thread) -> u64;
+        fn hpx_jthread_spawn(task: Box<ThreadTask>) -> UniquePtr<jthread>;
+        fn hpx_jthread_join(thread: Pin<&mut jthread>);
+        fn hpx_jthread_request_stop(thread: Pin<&mut jthread>) -> bool;
+        fn hpx_jthread_get_id(thread: &jthread) -> u64;
+        fn hpx_stop_token_stop_requested(token: &stop_token) -> bool;
+        fn hpx_stop_token_stop_possible(token: &stop_token) -> bool;
+        fn hpx_stop_token_clone(token: &stop_token) -> UniquePtr<stop_token>;
+        fn hpx_stop_source_new() -> UniquePtr<stop_source>;
+        fn hpx_stop_source_clone(source: &stop_source) -> UniquePtr<stop_source>;
+        fn hpx_stop_source_get_token(source: &stop_source) -> UniquePtr<stop_token>;
+        fn hpx_stop_source_request_stop(source: &stop_source) -> bool;
+        fn hpx_stop_source_stop_requested(source: &stop_source) -> bool;
+        fn hpx_stop_source_stop_possible(source: &stop_source) -> bool;
+        fn hpx_stop_callback_new(
+            token: &stop_token,
+            task: Box<ThreadTask>,
+        ) -> UniquePtr<hpx_stop_callback>;
+
+        type hpx_executor;
+        type hpx_future;
+
+        fn hpx_parallel_executor_new(
+            pool: &str,
+            priority: i8,
+            stacksize: i8,
+            hint_mode: i8,
+            hint: i16,
+        ) -> UniquePtr<hpx_executor>;
+        fn hpx_sequenced_executor_new() -> UniquePtr<hpx_executor>;
+        fn hpx_fork_join_executor_new() -> UniquePtr<hpx_executor>;
+        fn hpx_scheduler_executor_new() -> UniquePtr<hpx_executor>;
+        fn hpx_limiting_executor_new(lower: usize, upper: usize) -> UniquePtr<hpx_executor>;
+        fn hpx_executor_async_execute(
+            exec: &hpx_executor,
+            task: Box<ThreadTask>,
+        ) -> UniquePtr<hpx_future>;
+        fn hpx_executor_bulk_async_execute(
+            exec: &hpx_executor,
+            shape: usize,
+            task: Box<BulkTask>,
+        ) -> UniquePtr<hpx_future>;
+        fn hpx_executor_for_each_index(exec: &hpx_executor, len: usize, f: &IndexFunction);
+        fn hpx_future_wait(future: &hpx_future);
+        fn hpx_future_is_ready(future: &hpx_future) -> bool;
+
         fn hpx_copy(src: &[i32], dest: &mut [i32]);
         fn hpx_copy_n(src: &[i32], count: usize, dest: &mut [i32]);
-        fn hpx_copy_if(src: &Vec<i32>, dest: &mut Vec<i32>, pred: fn(i32) -> bool);
-        fn hpx_count(src: &Vec<i32>, value: i32) -> i64;
-        fn hpx_count_if(src: &Vec<i32>, pred: fn(i32) -> bool) -> i64;
+        fn hpx_copy_if(src: &[i32], dest: &mut [i32], pred: fn(i32) -> bool) -> Result<usize>;
+        fn hpx_count(src: &[i32], value: i32) -> i64;
+        fn hpx_count_if(src: &[i32], pred: fn(i32) -> bool) -> i64;
         fn hpx_ends_with(src: &[i32], dest: &[i32]) -> bool;
         fn hpx_equal(slice1: &[i32], slice2: &[i32]) -> bool;
         fn hpx_fill(src: &mut [i32], value: i32); // will only work for linear vectors
+        #[cxx_name = "hpx_replace"]
+        fn hpx_replace_i32(src: &mut [i32], old_value: i32, new_value: i32);
+        #[cxx_name = "hpx_replace"]
+        fn hpx_replace_i64(src: &mut [i64], old_value: i64, new_value: i64);
+        #[cxx_name = "hpx_replace"]
+        fn hpx_replace_u64(src: &mut [u64], old_value: u64, new_value: u64);
+        #[cxx_name = "hpx_replace"]
+        fn hpx_replace_f64(src: &mut [f64], old_value: f64, new_value: f64);
+        #[cxx_name = "hpx_replace_copy"]
+        fn hpx_replace_copy_i32(
+            src: &[i32],
+            dest: &mut [i32],
+            old_value: i32,
+            new_value: i32,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_replace_copy"]
+        fn hpx_replace_copy_i64(
+            src: &[i64],
+            dest: &mut [i64],
+            old_value: i64,
+            new_value: i64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_replace_copy"]
+        fn hpx_replace_copy_u64(
+            src: &[u64],
+            dest: &mut [u64],
+            old_value: u64,
+            new_value: u64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_replace_copy"]
+        fn hpx_replace_copy_f64(
+            src: &[f64],
+            dest: &mut [f64],
+            old_value: f64,
+            new_value: f64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_remove"]
+        fn hpx_remove_i32(src: &mut [i32], value: i32) -> usize;
+        #[cxx_name = "hpx_remove"]
+        fn hpx_remove_i64(src: &mut [i64], value: i64) -> usize;
+        #[cxx_name = "hpx_remove"]
+        fn hpx_remove_u64(src: &mut [u64], value: u64) -> usize;
+        #[cxx_name = "hpx_remove"]
+        fn hpx_remove_f64(src: &mut [f64], value: f64) -> usize;
+        #[cxx_name = "hpx_unique"]
+        fn hpx_unique_i32(src: &mut [i32]) -> usize;
+        #[cxx_name = "hpx_unique"]
+        fn hpx_unique_i64(src: &mut [i64]) -> usize;
+        #[cxx_name = "hpx_unique"]
+        fn hpx_unique_u64(src: &mut [u64]) -> usize;
+        #[cxx_name = "hpx_unique"]
+        fn hpx_unique_f64(src: &mut [f64]) -> usize;
+        #[cxx_name = "hpx_unique_copy"]
+        fn hpx_unique_copy_i32(src: &[i32], dest: &mut [i32]) -> Result<usize>;
+        #[cxx_name = "hpx_unique_copy"]
+        fn hpx_unique_copy_i64(src: &[i64], dest: &mut [i64]) -> Result<usize>;
+        #[cxx_name = "hpx_unique_copy"]
+        fn hpx_unique_copy_u64(src: &[u64], dest: &mut [u64]) -> Result<usize>;
+        #[cxx_name = "hpx_unique_copy"]
+        fn hpx_unique_copy_f64(src: &[f64], dest: &mut [f64]) -> Result<usize>;
+        #[cxx_name = "hpx_reverse"]
+        fn hpx_reverse_i32(src: &mut [i32]);
+        #[cxx_name = "hpx_reverse"]
+        fn hpx_reverse_i64(src: &mut [i64]);
+        #[cxx_name = "hpx_reverse"]
+        fn hpx_reverse_u64(src: &mut [u64]);
+        #[cxx_name = "hpx_reverse"]
+        fn hpx_reverse_f64(src: &mut [f64]);
+        #[cxx_name = "hpx_rotate"]
+        fn hpx_rotate_i32(src: &mut [i32], middle: usize) -> Result<()>;
+        #[cxx_name = "hpx_rotate"]
+        fn hpx_rotate_i64(src: &mut [i64], middle: usize) -> Result<()>;
+        #[cxx_name = "hpx_rotate"]
+        fn hpx_rotate_u64(src: &mut [u64], middle: usize) -> Result<()>;
+        #[cxx_name = "hpx_rotate"]
+        fn hpx_rotate_f64(src: &mut [f64], middle: usize) -> Result<()>;
+        #[cxx_name = "hpx_rotate_copy"]
+        fn hpx_rotate_copy_i32(src: &[i32], middle: usize, dest: &mut [i32]) -> Result<()>;
+        #[cxx_name = "hpx_rotate_copy"]
+        fn hpx_rotate_copy_i64(src: &[i64], middle: usize, dest: &mut [i64]) -> Result<()>;
+        #[cxx_name = "hpx_rotate_copy"]
+        fn hpx_rotate_copy_u64(src: &[u64], middle: usize, dest: &mut [u64]) -> Result<()>;
+        #[cxx_name = "hpx_rotate_copy"]
+        fn hpx_rotate_copy_f64(src: &[f64], middle: usize, dest: &mut [f64]) -> Result<()>;
+        #[cxx_name = "hpx_shift_left"]
+        fn hpx_shift_left_i32(src: &mut [i32], n: usize);
+        #[cxx_name = "hpx_shift_left"]
+        fn hpx_shift_left_i64(src: &mut [i64], n: usize);
+        #[cxx_name = "hpx_shift_left"]
+        fn hpx_shift_left_u64(src: &mut [u64], n: usize);
+        #[cxx_name = "hpx_shift_left"]
+        fn hpx_shift_left_f64(src: &mut [f64], n: usize);
+        #[cxx_name = "hpx_shift_right"]
+        fn hpx_shift_right_i32(src: &mut [i32], n: usize);
+        #[cxx_name = "hpx_shift_right"]
+        fn hpx_shift_right_i64(src: &mut [i64], n: usize);
+        #[cxx_name = "hpx_shift_right"]
+        fn hpx_shift_right_u64(src: &mut [u64], n: usize);
+        #[cxx_name = "hpx_shift_right"]
+        fn hpx_shift_right_f64(src: &mut [f64], n: usize);
+        #[cxx_name = "hpx_adjacent_difference"]
+        fn hpx_adjacent_difference_i32(
+            src: &[i32],
+            dest: &mut [i32],
+            op: &ValueOp_i32,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_adjacent_difference"]
+        fn hpx_adjacent_difference_i64(
+            src: &[i64],
+            dest: &mut [i64],
+            op: &ValueOp_i64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_adjacent_difference"]
+        fn hpx_adjacent_difference_u64(
+            src: &[u64],
+            dest: &mut [u64],
+            op: &ValueOp_u64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_adjacent_difference"]
+        fn hpx_adjacent_difference_f64(
+            src: &[f64],
+            dest: &mut [f64],
+            op: &ValueOp_f64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_iota"]
+        fn hpx_iota_i32(dest: &mut [i32], value: i32);
+        #[cxx_name = "hpx_iota"]
+        fn hpx_iota_i64(dest: &mut [i64], value: i64);
+        #[cxx_name = "hpx_iota"]
+        fn hpx_iota_u64(dest: &mut [u64], value: u64);
+        #[cxx_name = "hpx_iota"]
+        fn hpx_iota_f64(dest: &mut [f64], value: f64);
+        /// Copies `src` into the uninitialized memory at `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `src.len()` elements and must not
+        /// overlap `src`.
+        #[cxx_name = "hpx_uninitialized_copy"]
+        unsafe fn hpx_uninitialized_copy_i32(src: &[i32], dest: *mut i32);
+        /// Copies `src` into the uninitialized memory at `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `src.len()` elements and must not
+        /// overlap `src`.
+        #[cxx_name = "hpx_uninitialized_copy"]
+        unsafe fn hpx_uninitialized_copy_i64(src: &[i64], dest: *mut i64);
+        /// Copies `src` into the uninitialized memory at `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `src.len()` elements and must not
+        /// overlap `src`.
+        #[cxx_name = "hpx_uninitialized_copy"]
+        unsafe fn hpx_uninitialized_copy_u64(src: &[u64], dest: *mut u64);
+        /// Copies `src` into the uninitialized memory at `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `src.len()` elements and must not
+        /// overlap `src`.
+        #[cxx_name = "hpx_uninitialized_copy"]
+        unsafe fn hpx_uninitialized_copy_f64(src: &[f64], dest: *mut f64);
+        /// Writes `len` copies of `value` to the uninitialized memory at
+        /// `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `len` elements.
+        #[cxx_name = "hpx_uninitialized_fill"]
+        unsafe fn hpx_uninitialized_fill_i32(dest: *mut i32, len: usize, value: i32);
+        /// Writes `len` copies of `value` to the uninitialized memory at
+        /// `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `len` elements.
+        #[cxx_name = "hpx_uninitialized_fill"]
+        unsafe fn hpx_uninitialized_fill_i64(dest: *mut i64, len: usize, value: i64);
+        /// Writes `len` copies of `value` to the uninitialized memory at
+        /// `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `len` elements.
+        #[cxx_name = "hpx_uninitialized_fill"]
+        unsafe fn hpx_uninitialized_fill_u64(dest: *mut u64, len: usize, value: u64);
+        /// Writes `len` copies of `value` to the uninitialized memory at
+        /// `dest` in parallel.
+        ///
+        /// # Safety
+        ///
+        /// `dest` must be valid for writes of `len` elements.
+        #[cxx_name = "hpx_uninitialized_fill"]
+        unsafe fn hpx_uninitialized_fill_f64(dest: *mut f64, len: usize, value: f64);
         fn hpx_find(src: &[i32], value: i32) -> i64;
         fn hpx_sort(src: &mut [i32]);
-        fn hpx_sort_comp(src: &mut Vec<i32>, comp: fn(i32, i32) -> bool);
-        fn hpx_merge(src1: &[i32], src2: &[i32], dest: &mut Vec<i32>);
-        fn hpx_partial_sort(src: &mut Vec<i32>, last: usize);
-        fn hpx_partial_sort_comp(src: &mut Vec<i32>, last: usize, comp: fn(i32, i32) -> bool);
+        fn hpx_sort_comp(src: &mut [i32], comp: fn(i32, i32) -> bool);
+        fn hpx_merge(src1: &[i32], src2: &[i32], dest: &mut [i32]) -> Result<()>;
+        fn hpx_partial_sort(src: &mut [i32], last: usize);
+        fn hpx_partial_sort_comp(src: &mut [i32], last: usize, comp: fn(i32, i32) -> bool);
+        fn hpx_reduce_by_key_indices(
+            len: usize,
+            run_starts: &mut [usize],
+            eq: &IndexCompare,
+        ) -> Result<usize>;
+        fn hpx_sort_by_key_indices(
+            keys: &mut [usize],
+            values: &mut [usize],
+            comp: &IndexCompare,
+        ) -> Result<()>;
+        fn hpx_sort_indices(indices: &mut [usize], comp: &IndexCompare);
+        fn hpx_stable_sort_indices(indices: &mut [usize], comp: &IndexCompare);
+        fn hpx_nth_element_indices(
+            indices: &mut [usize],
+            nth: usize,
+            comp: &IndexCompare,
+        ) -> Result<()>;
+        fn hpx_partial_sort_copy_indices(
+            len: usize,
+            dest: &mut [usize],
+            comp: &IndexCompare,
+        ) -> usize;
+        fn hpx_is_sorted_indices(len: usize, comp: &IndexCompare) -> bool;
+        fn hpx_is_sorted_until_indices(len: usize, comp: &IndexCompare) -> usize;
+        fn hpx_merge_into(len1: usize, len2: usize, comp: &IndexCompare, place: &IndexPlace);
+        fn hpx_find_if_indices(len: usize, pred: &IndexPredicate) -> usize;
+        fn hpx_find_if_not_indices(len: usize, pred: &IndexPredicate) -> usize;
+        fn hpx_search_indices(len: usize, needle_len: usize, eq: &IndexCompare) -> usize;
+        fn hpx_find_end_indices(len: usize, needle_len: usize, eq: &IndexCompare) -> usize;
+        fn hpx_find_first_of_indices(len: usize, candidates_len: usize, eq: &IndexCompare)
+            -> usize;
+        fn hpx_adjacent_find_indices(len: usize, eq: &IndexCompare) -> usize;
+        fn hpx_mismatch_indices(len1: usize, len2: usize, eq: &IndexCompare) -> usize;
+        fn hpx_starts_with_indices(len: usize, prefix_len: usize, eq: &IndexCompare) -> bool;
+        fn hpx_all_of_indices(len: usize, pred: &IndexPredicate) -> bool;
+        fn hpx_any_of_indices(len: usize, pred: &IndexPredicate) -> bool;
+        fn hpx_none_of_indices(len: usize, pred: &IndexPredicate) -> bool;
+        fn hpx_min_element_indices(len: usize, comp: &IndexCompare) -> usize;
+        fn hpx_max_element_indices(len: usize, comp: &IndexCompare) -> usize;
+        fn hpx_minmax_element_indices(
+            len: usize,
+            comp: &IndexCompare,
+            min: &mut usize,
+            max: &mut usize,
+        );
+        fn hpx_is_partitioned_indices(len: usize, pred: &IndexPredicate) -> bool;
+        fn hpx_remove_if_indices(indices: &mut [usize], pred: &IndexPredicate) -> usize;
+        fn hpx_partition_indices(indices: &mut [usize], pred: &IndexPredicate) -> usize;
+        fn hpx_stable_partition_indices(indices: &mut [usize], pred: &IndexPredicate) -> usize;
+        fn hpx_partition_copy_indices(
+            len: usize,
+            dest_true: &mut [usize],
+            dest_false: &mut [usize],
+            pred: &IndexPredicate,
+        ) -> Result<usize>;
+        fn hpx_for_each_index(len: usize, f: &IndexFunction);
+        fn hpx_copy_if_indices(
+            len: usize,
+            dest: &mut [usize],
+            pred: &IndexPredicate,
+        ) -> Result<usize>;
+        fn hpx_count_if_indices(len: usize, pred: &IndexPredicate) -> usize;
+        #[cxx_name = "hpx_transform_reduce_indices"]
+        fn hpx_transform_reduce_indices_i32(
+            len: usize,
+            init: i32,
+            op: &ValueOp_i32,
+            conv: &IndexValue_i32,
+        ) -> i32;
+        #[cxx_name = "hpx_transform_reduce_indices"]
+        fn hpx_transform_reduce_indices_i64(
+            len: usize,
+            init: i64,
+            op: &ValueOp_i64,
+            conv: &IndexValue_i64,
+        ) -> i64;
+        #[cxx_name = "hpx_transform_reduce_indices"]
+        fn hpx_transform_reduce_indices_u64(
+            len: usize,
+            init: u64,
+            op: &ValueOp_u64,
+            conv: &IndexValue_u64,
+        ) -> u64;
+        #[cxx_name = "hpx_transform_reduce_indices"]
+        fn hpx_transform_reduce_indices_f64(
+            len: usize,
+            init: f64,
+            op: &ValueOp_f64,
+            conv: &IndexValue_f64,
+        ) -> f64;
+        fn hpx_make_heap_indices(indices: &mut [usize], comp: &IndexCompare);
+        fn hpx_is_heap_indices(len: usize, comp: &IndexCompare) -> bool;
+        fn hpx_is_heap_until_indices(len: usize, comp: &IndexCompare) -> usize;
+        fn hpx_equal_indices(len1: usize, len2: usize, eq: &IndexCompare) -> bool;
+        fn hpx_lexicographical_compare_indices(
+            first1: usize,
+            len1: usize,
+            first2: usize,
+            len2: usize,
+            comp: &IndexCompare,
+        ) -> bool;
+        fn hpx_set_union_indices(
+            len1: usize,
+            len2: usize,
+            dest: &mut [usize],
+            comp: &IndexCompare,
+        ) -> Result<usize>;
+        fn hpx_set_intersection_indices(
+            len1: usize,
+            len2: usize,
+            dest: &mut [usize],
+            comp: &IndexCompare,
+        ) -> Result<usize>;
+        fn hpx_set_difference_indices(
+            len1: usize,
+            len2: usize,
+            dest: &mut [usize],
+            comp: &IndexCompare,
+        ) -> Result<usize>;
+        fn hpx_set_symmetric_difference_indices(
+            len1: usize,
+            len2: usize,
+            dest: &mut [usize],
+            comp: &IndexCompare,
+        ) -> Result<usize>;
+        fn hpx_includes_indices(len1: usize, len2: usize, comp: &IndexCompare) -> bool;
+
+        #[cxx_name = "hpx_inclusive_scan"]
+        fn hpx_inclusive_scan_i32(src: &[i32], dest: &mut [i32], op: &ValueOp_i32) -> Result<()>;
+        #[cxx_name = "hpx_inclusive_scan"]
+        fn hpx_inclusive_scan_i64(src: &[i64], dest: &mut [i64], op: &ValueOp_i64) -> Result<()>;
+        #[cxx_name = "hpx_inclusive_scan"]
+        fn hpx_inclusive_scan_u64(src: &[u64], dest: &mut [u64], op: &ValueOp_u64) -> Result<()>;
+        #[cxx_name = "hpx_inclusive_scan"]
+        fn hpx_inclusive_scan_f64(src: &[f64], dest: &mut [f64], op: &ValueOp_f64) -> Result<()>;
+        #[cxx_name = "hpx_inclusive_scan_inplace"]
+        fn hpx_inclusive_scan_inplace_i32(src: &mut [i32], op: &ValueOp_i32);
+        #[cxx_name = "hpx_inclusive_scan_inplace"]
+        fn hpx_inclusive_scan_inplace_i64(src: &mut [i64], op: &ValueOp_i64);
+        #[cxx_name = "hpx_inclusive_scan_inplace"]
+        fn hpx_inclusive_scan_inplace_u64(src: &mut [u64], op: &ValueOp_u64);
+        #[cxx_name = "hpx_inclusive_scan_inplace"]
+        fn hpx_inclusive_scan_inplace_f64(src: &mut [f64], op: &ValueOp_f64);
+        #[cxx_name = "hpx_exclusive_scan"]
+        fn hpx_exclusive_scan_i32(
+            src: &[i32],
+            dest: &mut [i32],
+            init: i32,
+            op: &ValueOp_i32,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_exclusive_scan"]
+        fn hpx_exclusive_scan_i64(
+            src: &[i64],
+            dest: &mut [i64],
+            init: i64,
+            op: &ValueOp_i64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_exclusive_scan"]
+        fn hpx_exclusive_scan_u64(
+            src: &[u64],
+            dest: &mut [u64],
+            init: u64,
+            op: &ValueOp_u64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_exclusive_scan"]
+        fn hpx_exclusive_scan_f64(
+            src: &[f64],
+            dest: &mut [f64],
+            init: f64,
+            op: &ValueOp_f64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_exclusive_scan_inplace"]
+        fn hpx_exclusive_scan_inplace_i32(src: &mut [i32], init: i32, op: &ValueOp_i32);
+        #[cxx_name = "hpx_exclusive_scan_inplace"]
+        fn hpx_exclusive_scan_inplace_i64(src: &mut [i64], init: i64, op: &ValueOp_i64);
+        #[cxx_name = "hpx_exclusive_scan_inplace"]
+        fn hpx_exclusive_scan_inplace_u64(src: &mut [u64], init: u64, op: &ValueOp_u64);
+        #[cxx_name = "hpx_exclusive_scan_inplace"]
+        fn hpx_exclusive_scan_inplace_f64(src: &mut [f64], init: f64, op: &ValueOp_f64);
+        #[cxx_name = "hpx_transform_inclusive_scan"]
+        fn hpx_transform_inclusive_scan_i32(
+            src: &[i32],
+            dest: &mut [i32],
+            op: &ValueOp_i32,
+            conv: &ValueConv_i32,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_inclusive_scan"]
+        fn hpx_transform_inclusive_scan_i64(
+            src: &[i64],
+            dest: &mut [i64],
+            op: &ValueOp_i64,
+            conv: &ValueConv_i64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_inclusive_scan"]
+        fn hpx_transform_inclusive_scan_u64(
+            src: &[u64],
+            dest: &mut [u64],
+            op: &ValueOp_u64,
+            conv: &ValueConv_u64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_inclusive_scan"]
+        fn hpx_transform_inclusive_scan_f64(
+            src: &[f64],
+            dest: &mut [f64],
+            op: &ValueOp_f64,
+            conv: &ValueConv_f64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_inclusive_scan_inplace"]
+        fn hpx_transform_inclusive_scan_inplace_i32(
+            src: &mut [i32],
+            op: &ValueOp_i32,
+            conv: &ValueConv_i32,
+        );
+        #[cxx_name = "hpx_transform_inclusive_scan_inplace"]
+        fn hpx_transform_inclusive_scan_inplace_i64(
+            src: &mut [i64],
+            op: &ValueOp_i64,
+            conv: &ValueConv_i64,
+        );
+        #[cxx_name = "hpx_transform_inclusive_scan_inplace"]
+        fn hpx_transform_inclusive_scan_inplace_u64(
+            src: &mut [u64],
+            op: &ValueOp_u64,
+            conv: &ValueConv_u64,
+        );
+        #[cxx_name = "hpx_transform_inclusive_scan_inplace"]
+        fn hpx_transform_inclusive_scan_inplace_f64(
+            src: &mut [f64],
+            op: &ValueOp_f64,
+            conv: &ValueConv_f64,
+        );
+        #[cxx_name = "hpx_transform_exclusive_scan"]
+        fn hpx_transform_exclusive_scan_i32(
+            src: &[i32],
+            dest: &mut [i32],
+            init: i32,
+            op: &ValueOp_i32,
+            conv: &ValueConv_i32,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_exclusive_scan"]
+        fn hpx_transform_exclusive_scan_i64(
+            src: &[i64],
+            dest: &mut [i64],
+            init: i64,
+            op: &ValueOp_i64,
+            conv: &ValueConv_i64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_exclusive_scan"]
+        fn hpx_transform_exclusive_scan_u64(
+            src: &[u64],
+            dest: &mut [u64],
+            init: u64,
+            op: &ValueOp_u64,
+            conv: &ValueConv_u64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_exclusive_scan"]
+        fn hpx_transform_exclusive_scan_f64(
+            src: &[f64],
+            dest: &mut [f64],
+            init: f64,
+            op: &ValueOp_f64,
+            conv: &ValueConv_f64,
+        ) -> Result<()>;
+        #[cxx_name = "hpx_transform_exclusive_scan_inplace"]
+        fn hpx_transform_exclusive_scan_inplace_i32(
+            src: &mut [i32],
+            init: i32,
+            op: &ValueOp_i32,
+            conv: &ValueConv_i32,
+        );
+        #[cxx_name = "hpx_transform_exclusive_scan_inplace"]
+        fn hpx_transform_exclusive_scan_inplace_i64(
+            src: &mut [i64],
+            init: i64,
+            op: &ValueOp_i64,
+            conv: &ValueConv_i64,
+        );
+        #[cxx_name = "hpx_transform_exclusive_scan_inplace"]
+        fn hpx_transform_exclusive_scan_inplace_u64(
+            src: &mut [u64],
+            init: u64,
+            op: &ValueOp_u64,
+            conv: &ValueConv_u64,
+        );
+        #[cxx_name = "hpx_transform_exclusive_scan_inplace"]
+        fn hpx_transform_exclusive_scan_inplace_f64(
+            src: &mut [f64],
+            init: f64,
+            op: &ValueOp_f64,
+            conv: &ValueConv_f64,
+        );
     }
 }
 
@@ -39,8 +752,12 @@ pub mod ffi {
 // Wrapper for the above Bindings.
 // reffer to tests to understand how to use them. [NOTE: Not all bindings have wrapper.]
 // ================================================================================================
+use cxx::UniquePtr;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::env::Args;
 use std::ffi::{CStr, CString};
+use std::ops::Sub;
 use std::os::raw::c_char;
 
 static mut FUNC_HOLDER: Option<fn(Vec<String>) -> i32> = None;
@@ -80,29 +797,282 @@ pub fn create_c_args(args: &[&str]) -> (i32, Vec<*mut c_char>) {
     (ptrs.len() as i32, ptrs)
 }
 
+/// Binary predicate over element indices, handed to the index based bindings
+/// in [`ffi`] so HPX can order or group elements of any Rust type.
+///
+/// HPX calls it concurrently from its worker threads, hence the `Sync` bound.
+/// A panic inside the closure aborts the process.
+pub struct IndexCompare<'a>(&'a (dyn Fn(usize, usize) -> bool + Sync));
+
+impl<'a> IndexCompare<'a> {
+    pub fn new(comp: &'a (dyn Fn(usize, usize) -> bool + Sync)) -> Self {
+        IndexCompare(comp)
+    }
+
+    fn call(&self, a: usize, b: usize) -> bool {
+        (self.0)(a, b)
+    }
+}
+
+/// Unary predicate over element indices, the single argument counterpart of
+/// [`IndexCompare`].
+pub struct IndexPredicate<'a>(&'a (dyn Fn(usize) -> bool + Sync));
+
+impl<'a> IndexPredicate<'a> {
+    pub fn new(pred: &'a (dyn Fn(usize) -> bool + Sync)) -> Self {
+        IndexPredicate(pred)
+    }
+
+    fn call(&self, index: usize) -> bool {
+        (self.0)(index)
+    }
+}
+
+/// Callback run once for every index of a range, for algorithms such as
+/// [`generate`] that write each element independently.
+pub struct IndexFunction<'a>(&'a (dyn Fn(usize) + Sync));
+
+impl<'a> IndexFunction<'a> {
+    pub fn new(f: &'a (dyn Fn(usize) + Sync)) -> Self {
+        IndexFunction(f)
+    }
+
+    fn call(&self, index: usize) {
+        (self.0)(index)
+    }
+}
+
+/// Callback told that the element at index `src` goes to position `dest`, so
+/// algorithms such as [`merge_by`] move or copy elements straight into place
+/// instead of returning a permutation.
+pub struct IndexPlace<'a>(&'a (dyn Fn(usize, usize) + Sync));
+
+impl<'a> IndexPlace<'a> {
+    pub fn new(place: &'a (dyn Fn(usize, usize) + Sync)) -> Self {
+        IndexPlace(place)
+    }
+
+    fn call(&self, dest: usize, src: usize) {
+        (self.0)(dest, src)
+    }
+}
+
+/// Element valued callback over indices, e.g. the transformation applied by
+/// [`transform_reduce`] before reducing. The bridge sees one alias per
+/// [`NumericElement`] type.
+pub struct IndexValue<'a, T>(&'a (dyn Fn(usize) -> T + Sync));
+
+pub type IndexValue_i32<'a> = IndexValue<'a, i32>;
+pub type IndexValue_i64<'a> = IndexValue<'a, i64>;
+pub type IndexValue_u64<'a> = IndexValue<'a, u64>;
+pub type IndexValue_f64<'a> = IndexValue<'a, f64>;
+
+impl<'a, T> IndexValue<'a, T> {
+    pub fn new(f: &'a (dyn Fn(usize) -> T + Sync)) -> Self {
+        IndexValue(f)
+    }
+
+    fn call(&self, index: usize) -> T {
+        (self.0)(index)
+    }
+}
+
+/// Binary operation on element values, e.g. the `op` of [`inclusive_scan`]
+/// or [`transform_reduce`]. Unlike a `fn` pointer it may capture, say, a
+/// modulus. The bridge sees one alias per [`NumericElement`] type.
+pub struct ValueOp<'a, T>(&'a (dyn Fn(T, T) -> T + Sync));
+
+pub type ValueOp_i32<'a> = ValueOp<'a, i32>;
+pub type ValueOp_i64<'a> = ValueOp<'a, i64>;
+pub type ValueOp_u64<'a> = ValueOp<'a, u64>;
+pub type ValueOp_f64<'a> = ValueOp<'a, f64>;
+
+impl<'a, T> ValueOp<'a, T> {
+    pub fn new(op: &'a (dyn Fn(T, T) -> T + Sync)) -> Self {
+        ValueOp(op)
+    }
+
+    fn call(&self, a: T, b: T) -> T {
+        (self.0)(a, b)
+    }
+}
+
+/// Conversion applied to every element value, the `conv` of
+/// [`transform_inclusive_scan`] and [`transform_exclusive_scan`], with
+/// aliases as for [`ValueOp`].
+pub struct ValueConv<'a, T>(&'a (dyn Fn(T) -> T + Sync));
+
+pub type ValueConv_i32<'a> = ValueConv<'a, i32>;
+pub type ValueConv_i64<'a> = ValueConv<'a, i64>;
+pub type ValueConv_u64<'a> = ValueConv<'a, u64>;
+pub type ValueConv_f64<'a> = ValueConv<'a, f64>;
+
+impl<'a, T> ValueConv<'a, T> {
+    pub fn new(conv: &'a (dyn Fn(T) -> T + Sync)) -> Self {
+        ValueConv(conv)
+    }
+
+    fn call(&self, value: T) -> T {
+        (self.0)(value)
+    }
+}
+
+/// Element pointer shared by the HPX worker threads of the index based
+/// algorithms, each of which touches a distinct element. Shared with the
+/// parallel iterators of `hpx-rs`, hence public.
+#[doc(hidden)]
+pub struct SharedMutPtr<T>(*mut T);
+
+unsafe impl<T: Send> Sync for SharedMutPtr<T> {}
+
+impl<T> SharedMutPtr<T> {
+    pub fn new(ptr: *mut T) -> Self {
+        SharedMutPtr(ptr)
+    }
+
+    pub fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+/// A closure run by [`ffi::hpx_executor_bulk_async_execute`] on every index
+/// of its shape, possibly after the call has returned.
+pub struct BulkTask(Box<dyn Fn(usize) + Send + Sync>);
+
+impl BulkTask {
+    pub fn new(f: impl Fn(usize) + Send + Sync + 'static) -> Box<Self> {
+        Box::new(BulkTask(Box::new(f)))
+    }
+
+    fn call(&self, index: usize) {
+        (self.0)(index)
+    }
+}
+
+/// The closure behind a counter type installed by
+/// [`ffi::hpx_install_counter_type`], returning the current value and
+/// resetting it if asked to. HPX may call it from any thread.
+pub struct CounterCallback(Box<dyn Fn(bool) -> i64 + Send + Sync>);
+
+impl CounterCallback {
+    pub fn new(f: impl Fn(bool) -> i64 + Send + Sync + 'static) -> Box<Self> {
+        Box::new(CounterCallback(Box::new(f)))
+    }
+
+    fn call(&self, reset: bool) -> i64 {
+        (self.0)(reset)
+    }
+}
+
+/// A closure run once on a new HPX thread by [`ffi::hpx_post`],
+/// [`ffi::hpx_thread_spawn`], [`ffi::hpx_jthread_spawn`] or
+/// [`ffi::hpx_executor_async_execute`], or by the thread requesting a stop for
+/// [`ffi::hpx_stop_callback_new`]. Only
+/// `hpx_jthread_spawn` passes it the stop token of the thread.
+pub struct ThreadTask(Option<Box<ThreadFn>>);
+
+type ThreadFn = dyn FnOnce(Option<UniquePtr<ffi::stop_token>>) + Send;
+
+impl ThreadTask {
+    pub fn new(f: impl FnOnce(Option<UniquePtr<ffi::stop_token>>) + Send + 'static) -> Box<Self> {
+        Box::new(ThreadTask(Some(Box::new(f))))
+    }
+
+    fn run(&mut self) {
+        if let Some(f) = self.0.take() {
+            f(None)
+        }
+    }
+
+    fn run_with_stop_token(&mut self, token: UniquePtr<ffi::stop_token>) {
+        if let Some(f) = self.0.take() {
+            f(Some(token))
+        }
+    }
+}
+
+// `hpx::thread`, `hpx::jthread`, `hpx::stop_token`, `hpx::stop_source`,
+// `hpx::stop_callback` and `hpx::future` may be used from any thread, like
+// their `std` counterparts, and so may the HPX executors.
+unsafe impl Send for ffi::thread {}
+unsafe impl Sync for ffi::thread {}
+unsafe impl Send for ffi::jthread {}
+unsafe impl Sync for ffi::jthread {}
+unsafe impl Send for ffi::stop_token {}
+unsafe impl Sync for ffi::stop_token {}
+unsafe impl Send for ffi::stop_source {}
+unsafe impl Sync for ffi::stop_source {}
+unsafe impl Send for ffi::hpx_stop_callback {}
+unsafe impl Sync for ffi::hpx_stop_callback {}
+// Not `Sync`: whether an executor may be used from several threads at once
+// depends on its kind, so the wrappers in `hpx-rs` decide.
+unsafe impl Send for ffi::hpx_executor {}
+unsafe impl Send for ffi::hpx_future {}
+unsafe impl Sync for ffi::hpx_future {}
+// A performance counter is a client holding the global id of the counter.
+unsafe impl Send for ffi::hpx_performance_counter {}
+unsafe impl Sync for ffi::hpx_performance_counter {}
+
+// Map the "not found" position returned by the index based bindings, which is
+// the length of the searched range, to `None`.
+fn found(index: usize, len: usize) -> Option<usize> {
+    if index == len {
+        None
+    } else {
+        Some(index)
+    }
+}
+
+// Reorder `src` so that `src[i]` becomes the old `src[perm[i]]`. `perm` is
+// consumed: every entry ends up pointing at itself. Panics if `perm` is not a
+// permutation of `0..src.len()`, which the cycle walk below would otherwise
+// never finish on.
+fn apply_permutation<T>(src: &mut [T], perm: &mut [usize]) {
+    assert_eq!(
+        src.len(),
+        perm.len(),
+        "permutation length differs from slice length"
+    );
+    let mut seen = vec![false; perm.len()];
+    for &index in perm.iter() {
+        assert!(
+            index < seen.len() && !std::mem::replace(&mut seen[index], true),
+            "index reordering returned an invalid permutation"
+        );
+    }
+    for start in 0..perm.len() {
+        let mut current = start;
+        while perm[current] != start {
+            let next = perm[current];
+            src.swap(current, next);
+            perm[current] = current;
+            current = next;
+        }
+        perm[current] = current;
+    }
+}
+
 pub fn copy_vector(src: &[i32]) -> Vec<i32> {
-    let mut dest = vec![0; src.len()];
-    ffi::hpx_copy(src, &mut dest);
-    dest
+    uninitialized_copy(src)
 }
 
 pub fn copy_n(src: &[i32], count: usize) -> Result<Vec<i32>, &'static str> {
     if count > src.len() {
         return Err("count larger than source slice length");
     }
-    let mut dest = vec![0; count];
-    ffi::hpx_copy_n(src, count, &mut dest);
-    Ok(dest)
+    Ok(uninitialized_copy(&src[..count]))
 }
 
-pub fn copy_if_divisiblileityby3(src: &Vec<i32>) -> Vec<i32> {
-    let mut dest = Vec::new();
-    ffi::hpx_copy_if(src, &mut dest, |x| x % 3 == 0);
+pub fn copy_if_divisiblileityby3(src: &[i32]) -> Vec<i32> {
+    let mut dest = vec![0; src.len()];
+    let len = ffi::hpx_copy_if(src, &mut dest, |x| x % 3 == 0)
+        .expect("destination is as long as the source");
+    dest.truncate(len);
     dest
 }
 
-pub fn count(vec: &Vec<i32>, value: i32) -> i64 {
-    ffi::hpx_count(vec, value)
+pub fn count(slice: &[i32], value: i32) -> i64 {
+    ffi::hpx_count(slice, value)
 }
 
 pub fn find(slice: &[i32], value: i32) -> Option<usize> {
@@ -113,49 +1083,2621 @@ pub fn find(slice: &[i32], value: i32) -> Option<usize> {
 }
 
 pub fn merge(src1: &[i32], src2: &[i32]) -> Vec<i32> {
-    let mut dest = Vec::new();
-    ffi::hpx_merge(src1, src2, &mut dest);
+    let mut dest = vec![0; src1.len() + src2.len()];
+    ffi::hpx_merge(src1, src2, &mut dest).expect("destination is as long as both sources");
     dest
 }
 
-// ================================================================================================
-// Tests (to be shifted to systests crate within hpx-rs workspace)
-// ================================================================================================
-#[cfg(test)]
-mod tests {
-    use super::ffi;
-    use crate::{copy_if_divisiblileityby3, copy_n, copy_vector, count, create_c_args, find};
-    use serial_test::serial;
-    use std::ffi::CString;
-    use std::os::raw::c_char;
+/// Element types with typed bindings in [`ffi`], for the algorithms that
+/// compute or move element values rather than just compare them.
+///
+/// The methods forward to the matching `ffi` binding for `Self`; use the free
+/// functions such as [`inclusive_scan`] instead of calling them directly.
+pub trait NumericElement: Copy + Default + Send + Sync {
+    #[doc(hidden)]
+    fn inclusive_scan(
+        src: &[Self],
+        dest: &mut [Self],
+        op: &ValueOp<Self>,
+    ) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn inclusive_scan_inplace(src: &mut [Self], op: &ValueOp<Self>);
+    #[doc(hidden)]
+    fn exclusive_scan(
+        src: &[Self],
+        dest: &mut [Self],
+        init: Self,
+        op: &ValueOp<Self>,
+    ) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn exclusive_scan_inplace(src: &mut [Self], init: Self, op: &ValueOp<Self>);
+    #[doc(hidden)]
+    fn transform_inclusive_scan(
+        src: &[Self],
+        dest: &mut [Self],
+        op: &ValueOp<Self>,
+        conv: &ValueConv<Self>,
+    ) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn transform_inclusive_scan_inplace(
+        src: &mut [Self],
+        op: &ValueOp<Self>,
+        conv: &ValueConv<Self>,
+    );
+    #[doc(hidden)]
+    fn transform_exclusive_scan(
+        src: &[Self],
+        dest: &mut [Self],
+        init: Self,
+        op: &ValueOp<Self>,
+        conv: &ValueConv<Self>,
+    ) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn transform_exclusive_scan_inplace(
+        src: &mut [Self],
+        init: Self,
+        op: &ValueOp<Self>,
+        conv: &ValueConv<Self>,
+    );
+    #[doc(hidden)]
+    fn replace(src: &mut [Self], old_value: Self, new_value: Self);
+    #[doc(hidden)]
+    fn replace_copy(
+        src: &[Self],
+        dest: &mut [Self],
+        old_value: Self,
+        new_value: Self,
+    ) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn remove(src: &mut [Self], value: Self) -> usize;
+    #[doc(hidden)]
+    fn unique(src: &mut [Self]) -> usize;
+    #[doc(hidden)]
+    fn unique_copy(src: &[Self], dest: &mut [Self]) -> Result<usize, cxx::Exception>;
+    #[doc(hidden)]
+    fn reverse(src: &mut [Self]);
+    #[doc(hidden)]
+    fn rotate(src: &mut [Self], middle: usize) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn rotate_copy(src: &[Self], middle: usize, dest: &mut [Self]) -> Result<(), cxx::Exception>;
+    #[doc(hidden)]
+    fn shift_left(src: &mut [Self], n: usize);
+    #[doc(hidden)]
+    fn shift_right(src: &mut [Self], n: usize);
+    #[doc(hidden)]
+    fn iota(dest: &mut [Self], value: Self);
+    #[doc(hidden)]
+    fn adjacent_difference(
+        src: &[Self],
+        dest: &mut [Self],
+        op: &ValueOp<Self>,
+    ) -> Result<(), cxx::Exception>;
+    /// # Safety
+    ///
+    /// `dest` must be valid for writes of `src.len()` elements and must not
+    /// overlap `src`.
+    #[doc(hidden)]
+    unsafe fn uninitialized_copy(src: &[Self], dest: *mut Self);
+    /// # Safety
+    ///
+    /// `dest` must be valid for writes of `len` elements.
+    #[doc(hidden)]
+    unsafe fn uninitialized_fill(dest: *mut Self, len: usize, value: Self);
+    #[doc(hidden)]
+    fn transform_reduce(
+        len: usize,
+        init: Self,
+        op: &ValueOp<Self>,
+        conv: &IndexValue<Self>,
+    ) -> Self;
+}
 
-    #[test]
-    #[serial]
-    fn test_init_finalize() {
-        let (argc, mut argv) = create_c_args(&["testing", "arg1", "arg2"]);
+macro_rules! impl_numeric_element {
+    ($t:ty {
+        inclusive_scan: $inclusive_scan:ident,
+        inclusive_scan_inplace: $inclusive_scan_inplace:ident,
+        exclusive_scan: $exclusive_scan:ident,
+        exclusive_scan_inplace: $exclusive_scan_inplace:ident,
+        transform_inclusive_scan: $transform_inclusive_scan:ident,
+        transform_inclusive_scan_inplace: $transform_inclusive_scan_inplace:ident,
+        transform_exclusive_scan: $transform_exclusive_scan:ident,
+        transform_exclusive_scan_inplace: $transform_exclusive_scan_inplace:ident,
+        replace: $replace:ident,
+        replace_copy: $replace_copy:ident,
+        remove: $remove:ident,
+        unique: $unique:ident,
+        unique_copy: $unique_copy:ident,
+        reverse: $reverse:ident,
+        rotate: $rotate:ident,
+        rotate_copy: $rotate_copy:ident,
+        shift_left: $shift_left:ident,
+        shift_right: $shift_right:ident,
+        iota: $iota:ident,
+        adjacent_difference: $adjacent_difference:ident,
+        uninitialized_copy: $uninitialized_copy:ident,
+        uninitialized_fill: $uninitialized_fill:ident,
+        transform_reduce: $transform_reduce:ident,
+    }) => {
+        impl NumericElement for $t {
+            fn inclusive_scan(
+                src: &[Self],
+                dest: &mut [Self],
+                op: &ValueOp<Self>,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$inclusive_scan(src, dest, op)
+            }
 
-        let dummy_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            println!("Dummy fn called");
-            // to exit hpx::init you are required to shutdown hpx runtime
-            ffi::finalize();
-            0
+            fn inclusive_scan_inplace(src: &mut [Self], op: &ValueOp<Self>) {
+                ffi::$inclusive_scan_inplace(src, op)
+            }
+
+            fn exclusive_scan(
+                src: &[Self],
+                dest: &mut [Self],
+                init: Self,
+                op: &ValueOp<Self>,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$exclusive_scan(src, dest, init, op)
+            }
+
+            fn exclusive_scan_inplace(src: &mut [Self], init: Self, op: &ValueOp<Self>) {
+                ffi::$exclusive_scan_inplace(src, init, op)
+            }
+
+            fn transform_inclusive_scan(
+                src: &[Self],
+                dest: &mut [Self],
+                op: &ValueOp<Self>,
+                conv: &ValueConv<Self>,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$transform_inclusive_scan(src, dest, op, conv)
+            }
+
+            fn transform_inclusive_scan_inplace(
+                src: &mut [Self],
+                op: &ValueOp<Self>,
+                conv: &ValueConv<Self>,
+            ) {
+                ffi::$transform_inclusive_scan_inplace(src, op, conv)
+            }
+
+            fn transform_exclusive_scan(
+                src: &[Self],
+                dest: &mut [Self],
+                init: Self,
+                op: &ValueOp<Self>,
+                conv: &ValueConv<Self>,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$transform_exclusive_scan(src, dest, init, op, conv)
+            }
+
+            fn transform_exclusive_scan_inplace(
+                src: &mut [Self],
+                init: Self,
+                op: &ValueOp<Self>,
+                conv: &ValueConv<Self>,
+            ) {
+                ffi::$transform_exclusive_scan_inplace(src, init, op, conv)
+            }
+
+            fn replace(src: &mut [Self], old_value: Self, new_value: Self) {
+                ffi::$replace(src, old_value, new_value)
+            }
+            fn replace_copy(
+                src: &[Self],
+                dest: &mut [Self],
+                old_value: Self,
+                new_value: Self,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$replace_copy(src, dest, old_value, new_value)
+            }
+            fn remove(src: &mut [Self], value: Self) -> usize {
+                ffi::$remove(src, value)
+            }
+            fn unique(src: &mut [Self]) -> usize {
+                ffi::$unique(src)
+            }
+            fn unique_copy(src: &[Self], dest: &mut [Self]) -> Result<usize, cxx::Exception> {
+                ffi::$unique_copy(src, dest)
+            }
+            fn reverse(src: &mut [Self]) {
+                ffi::$reverse(src)
+            }
+            fn rotate(src: &mut [Self], middle: usize) -> Result<(), cxx::Exception> {
+                ffi::$rotate(src, middle)
+            }
+            fn rotate_copy(
+                src: &[Self],
+                middle: usize,
+                dest: &mut [Self],
+            ) -> Result<(), cxx::Exception> {
+                ffi::$rotate_copy(src, middle, dest)
+            }
+            fn shift_left(src: &mut [Self], n: usize) {
+                ffi::$shift_left(src, n)
+            }
+            fn shift_right(src: &mut [Self], n: usize) {
+                ffi::$shift_right(src, n)
+            }
+
+            fn iota(dest: &mut [Self], value: Self) {
+                ffi::$iota(dest, value)
+            }
+
+            fn adjacent_difference(
+                src: &[Self],
+                dest: &mut [Self],
+                op: &ValueOp<Self>,
+            ) -> Result<(), cxx::Exception> {
+                ffi::$adjacent_difference(src, dest, op)
+            }
+
+            unsafe fn uninitialized_copy(src: &[Self], dest: *mut Self) {
+                ffi::$uninitialized_copy(src, dest)
+            }
+
+            unsafe fn uninitialized_fill(dest: *mut Self, len: usize, value: Self) {
+                ffi::$uninitialized_fill(dest, len, value)
+            }
+
+            fn transform_reduce(
+                len: usize,
+                init: Self,
+                op: &ValueOp<Self>,
+                conv: &IndexValue<Self>,
+            ) -> Self {
+                ffi::$transform_reduce(len, init, op, conv)
+            }
+        }
+    };
+}
+
+impl_numeric_element!(i32 {
+    inclusive_scan: hpx_inclusive_scan_i32,
+    inclusive_scan_inplace: hpx_inclusive_scan_inplace_i32,
+    exclusive_scan: hpx_exclusive_scan_i32,
+    exclusive_scan_inplace: hpx_exclusive_scan_inplace_i32,
+    transform_inclusive_scan: hpx_transform_inclusive_scan_i32,
+    transform_inclusive_scan_inplace: hpx_transform_inclusive_scan_inplace_i32,
+    transform_exclusive_scan: hpx_transform_exclusive_scan_i32,
+    transform_exclusive_scan_inplace: hpx_transform_exclusive_scan_inplace_i32,
+    replace: hpx_replace_i32,
+    replace_copy: hpx_replace_copy_i32,
+    remove: hpx_remove_i32,
+    unique: hpx_unique_i32,
+    unique_copy: hpx_unique_copy_i32,
+    reverse: hpx_reverse_i32,
+    rotate: hpx_rotate_i32,
+    rotate_copy: hpx_rotate_copy_i32,
+    shift_left: hpx_shift_left_i32,
+    shift_right: hpx_shift_right_i32,
+    iota: hpx_iota_i32,
+    adjacent_difference: hpx_adjacent_difference_i32,
+    uninitialized_copy: hpx_uninitialized_copy_i32,
+    uninitialized_fill: hpx_uninitialized_fill_i32,
+    transform_reduce: hpx_transform_reduce_indices_i32,
+});
+
+impl_numeric_element!(i64 {
+    inclusive_scan: hpx_inclusive_scan_i64,
+    inclusive_scan_inplace: hpx_inclusive_scan_inplace_i64,
+    exclusive_scan: hpx_exclusive_scan_i64,
+    exclusive_scan_inplace: hpx_exclusive_scan_inplace_i64,
+    transform_inclusive_scan: hpx_transform_inclusive_scan_i64,
+    transform_inclusive_scan_inplace: hpx_transform_inclusive_scan_inplace_i64,
+    transform_exclusive_scan: hpx_transform_exclusive_scan_i64,
+    transform_exclusive_scan_inplace: hpx_transform_exclusive_scan_inplace_i64,
+    replace: hpx_replace_i64,
+    replace_copy: hpx_replace_copy_i64,
+    remove: hpx_remove_i64,
+    unique: hpx_unique_i64,
+    unique_copy: hpx_unique_copy_i64,
+    reverse: hpx_reverse_i64,
+    rotate: hpx_rotate_i64,
+    rotate_copy: hpx_rotate_copy_i64,
+    shift_left: hpx_shift_left_i64,
+    shift_right: hpx_shift_right_i64,
+    iota: hpx_iota_i64,
+    adjacent_difference: hpx_adjacent_difference_i64,
+    uninitialized_copy: hpx_uninitialized_copy_i64,
+    uninitialized_fill: hpx_uninitialized_fill_i64,
+    transform_reduce: hpx_transform_reduce_indices_i64,
+});
+
+impl_numeric_element!(u64 {
+    inclusive_scan: hpx_inclusive_scan_u64,
+    inclusive_scan_inplace: hpx_inclusive_scan_inplace_u64,
+    exclusive_scan: hpx_exclusive_scan_u64,
+    exclusive_scan_inplace: hpx_exclusive_scan_inplace_u64,
+    transform_inclusive_scan: hpx_transform_inclusive_scan_u64,
+    transform_inclusive_scan_inplace: hpx_transform_inclusive_scan_inplace_u64,
+    transform_exclusive_scan: hpx_transform_exclusive_scan_u64,
+    transform_exclusive_scan_inplace: hpx_transform_exclusive_scan_inplace_u64,
+    replace: hpx_replace_u64,
+    replace_copy: hpx_replace_copy_u64,
+    remove: hpx_remove_u64,
+    unique: hpx_unique_u64,
+    unique_copy: hpx_unique_copy_u64,
+    reverse: hpx_reverse_u64,
+    rotate: hpx_rotate_u64,
+    rotate_copy: hpx_rotate_copy_u64,
+    shift_left: hpx_shift_left_u64,
+    shift_right: hpx_shift_right_u64,
+    iota: hpx_iota_u64,
+    adjacent_difference: hpx_adjacent_difference_u64,
+    uninitialized_copy: hpx_uninitialized_copy_u64,
+    uninitialized_fill: hpx_uninitialized_fill_u64,
+    transform_reduce: hpx_transform_reduce_indices_u64,
+});
+
+impl_numeric_element!(f64 {
+    inclusive_scan: hpx_inclusive_scan_f64,
+    inclusive_scan_inplace: hpx_inclusive_scan_inplace_f64,
+    exclusive_scan: hpx_exclusive_scan_f64,
+    exclusive_scan_inplace: hpx_exclusive_scan_inplace_f64,
+    transform_inclusive_scan: hpx_transform_inclusive_scan_f64,
+    transform_inclusive_scan_inplace: hpx_transform_inclusive_scan_inplace_f64,
+    transform_exclusive_scan: hpx_transform_exclusive_scan_f64,
+    transform_exclusive_scan_inplace: hpx_transform_exclusive_scan_inplace_f64,
+    replace: hpx_replace_f64,
+    replace_copy: hpx_replace_copy_f64,
+    remove: hpx_remove_f64,
+    unique: hpx_unique_f64,
+    unique_copy: hpx_unique_copy_f64,
+    reverse: hpx_reverse_f64,
+    rotate: hpx_rotate_f64,
+    rotate_copy: hpx_rotate_copy_f64,
+    shift_left: hpx_shift_left_f64,
+    shift_right: hpx_shift_right_f64,
+    iota: hpx_iota_f64,
+    adjacent_difference: hpx_adjacent_difference_f64,
+    uninitialized_copy: hpx_uninitialized_copy_f64,
+    uninitialized_fill: hpx_uninitialized_fill_f64,
+    transform_reduce: hpx_transform_reduce_indices_f64,
+});
+
+/// Parallel inclusive prefix scan: element `i` of the result is
+/// `src[0] op src[1] op ... op src[i]`.
+///
+/// `op` must be associative. HPX scans each chunk of `src` on a separate
+/// worker and then combines the chunk totals, so the grouping of the
+/// applications of `op` is unspecified. A non-associative `op` such as
+/// subtraction gives results that depend on the chunking, and floating point
+/// addition may differ from a sequential scan in the last bits.
+pub fn inclusive_scan<T: NumericElement>(src: &[T], op: impl Fn(T, T) -> T + Sync) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::inclusive_scan(src, &mut dest, &ValueOp::new(&op))
+        .expect("destination is as long as the source");
+    dest
+}
+
+/// In-place form of [`inclusive_scan`], with the same requirements on `op`.
+pub fn inclusive_scan_inplace<T: NumericElement>(src: &mut [T], op: impl Fn(T, T) -> T + Sync) {
+    T::inclusive_scan_inplace(src, &ValueOp::new(&op));
+}
+
+/// Parallel exclusive prefix scan: element `i` of the result is
+/// `init op src[0] op ... op src[i - 1]`, so the first element is `init`.
+///
+/// `op` must be associative, see [`inclusive_scan`].
+pub fn exclusive_scan<T: NumericElement>(
+    src: &[T],
+    init: T,
+    op: impl Fn(T, T) -> T + Sync,
+) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::exclusive_scan(src, &mut dest, init, &ValueOp::new(&op))
+        .expect("destination is as long as the source");
+    dest
+}
+
+/// In-place form of [`exclusive_scan`], with the same requirements on `op`.
+pub fn exclusive_scan_inplace<T: NumericElement>(
+    src: &mut [T],
+    init: T,
+    op: impl Fn(T, T) -> T + Sync,
+) {
+    T::exclusive_scan_inplace(src, init, &ValueOp::new(&op));
+}
+
+/// Like [`inclusive_scan`], but every element is passed through `conv` before
+/// it is combined with `op`.
+pub fn transform_inclusive_scan<T: NumericElement>(
+    src: &[T],
+    op: impl Fn(T, T) -> T + Sync,
+    conv: impl Fn(T) -> T + Sync,
+) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::transform_inclusive_scan(src, &mut dest, &ValueOp::new(&op), &ValueConv::new(&conv))
+        .expect("destination is as long as the source");
+    dest
+}
+
+/// In-place form of [`transform_inclusive_scan`].
+pub fn transform_inclusive_scan_inplace<T: NumericElement>(
+    src: &mut [T],
+    op: impl Fn(T, T) -> T + Sync,
+    conv: impl Fn(T) -> T + Sync,
+) {
+    T::transform_inclusive_scan_inplace(src, &ValueOp::new(&op), &ValueConv::new(&conv));
+}
+
+/// Like [`exclusive_scan`], but every element is passed through `conv` before
+/// it is combined with `op`. `init` itself is not converted.
+pub fn transform_exclusive_scan<T: NumericElement>(
+    src: &[T],
+    init: T,
+    op: impl Fn(T, T) -> T + Sync,
+    conv: impl Fn(T) -> T + Sync,
+) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::transform_exclusive_scan(
+        src,
+        &mut dest,
+        init,
+        &ValueOp::new(&op),
+        &ValueConv::new(&conv),
+    )
+    .expect("destination is as long as the source");
+    dest
+}
+
+/// In-place form of [`transform_exclusive_scan`].
+pub fn transform_exclusive_scan_inplace<T: NumericElement>(
+    src: &mut [T],
+    init: T,
+    op: impl Fn(T, T) -> T + Sync,
+    conv: impl Fn(T) -> T + Sync,
+) {
+    T::transform_exclusive_scan_inplace(src, init, &ValueOp::new(&op), &ValueConv::new(&conv));
+}
+
+/// Reduces every run of consecutive equal keys to a single key/value pair.
+///
+/// Keys are compared with `eq` and the values of a run are combined with
+/// `op`, which must be associative (see [`inclusive_scan`]). HPX finds the
+/// runs in parallel and the runs are then reduced in parallel with each
+/// other. The reduced pairs are moved to the front of `keys` and `values` and
+/// their number is returned; everything past it is left in an unspecified
+/// order. Sort by key first (e.g. with [`sort_by_key`]) to get one pair per
+/// distinct key.
+pub fn reduce_by_key<K: Sync, V: Clone + Send + Sync>(
+    keys: &mut [K],
+    values: &mut [V],
+    eq: impl Fn(&K, &K) -> bool + Sync,
+    op: impl Fn(&V, &V) -> V + Sync,
+) -> Result<usize, &'static str> {
+    if keys.len() != values.len() {
+        return Err("keys and values differ in length");
+    }
+    let mut run_starts = vec![0; keys.len()];
+    let runs = {
+        let key_slice: &[K] = keys;
+        let eq_indices = |a: usize, b: usize| eq(&key_slice[a], &key_slice[b]);
+        ffi::hpx_reduce_by_key_indices(keys.len(), &mut run_starts, &IndexCompare::new(&eq_indices))
+            .expect("destination is as long as the keys")
+    };
+    let run_starts = &run_starts[..runs];
+
+    let value_slice: &[V] = values;
+    let reduced = generate_vec(runs, |run| {
+        let end = run_starts
+            .get(run + 1)
+            .copied()
+            .unwrap_or(value_slice.len());
+        let run = &value_slice[run_starts[run]..end];
+        run[1..]
+            .iter()
+            .fold(run[0].clone(), |acc, value| op(&acc, value))
+    });
+    for (slot, value) in values.iter_mut().zip(reduced) {
+        *slot = value;
+    }
+    // The run starts are increasing, so every swap source is still untouched.
+    for (i, &index) in run_starts.iter().enumerate() {
+        keys.swap(i, index);
+    }
+    Ok(runs)
+}
+
+/// Sorts `keys` with `less` and applies the same reordering to `values`.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`stable_sort`].
+pub unsafe fn sort_by_key<K: Sync, V>(
+    keys: &mut [K],
+    values: &mut [V],
+    less: impl Fn(&K, &K) -> bool + Sync,
+) -> Result<(), &'static str> {
+    if keys.len() != values.len() {
+        return Err("keys and values differ in length");
+    }
+    let mut key_perm: Vec<usize> = (0..keys.len()).collect();
+    let mut value_perm = key_perm.clone();
+    {
+        let key_slice: &[K] = keys;
+        let comp = |a: usize, b: usize| less(&key_slice[a], &key_slice[b]);
+        ffi::hpx_sort_by_key_indices(&mut key_perm, &mut value_perm, &IndexCompare::new(&comp))
+            .expect("values are as long as the keys");
+    }
+    apply_permutation(keys, &mut key_perm);
+    apply_permutation(values, &mut value_perm);
+    Ok(())
+}
+
+// Reorder `src` by running `reorder` (one of the index based bindings) on a
+// permutation of its indices and then moving the elements into place.
+//
+// Safety: `less` must be a strict weak ordering, see [`stable_sort`].
+unsafe fn permute_with<T: Sync>(
+    src: &mut [T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+    reorder: impl FnOnce(&mut [usize], &IndexCompare),
+) {
+    let mut perm: Vec<usize> = (0..src.len()).collect();
+    {
+        let elements: &[T] = src;
+        let comp = |a: usize, b: usize| less(&elements[a], &elements[b]);
+        reorder(&mut perm, &IndexCompare::new(&comp));
+    }
+    apply_permutation(src, &mut perm);
+}
+
+/// Sorts `src` in parallel with the strict weak ordering `less`, keeping equal
+/// elements in their original order.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering on the elements of `src`: irreflexive,
+/// transitive, and with incomparability transitive as well. The C++ sort
+/// relies on this to stay within the index array, so any other comparator,
+/// such as `<` on floats that include NaN, is undefined behaviour. The same
+/// contract applies to the other sorting, heap and merge functions taking a
+/// `less` closure.
+pub unsafe fn stable_sort<T: Sync>(src: &mut [T], less: impl Fn(&T, &T) -> bool + Sync) {
+    permute_with(src, less, ffi::hpx_stable_sort_indices);
+}
+
+/// Reorders `src` so that `src[nth]` is the element that would be there if
+/// `src` were sorted by `less`, with no element before it greater and no
+/// element after it less than it. Useful for parallel medians and quantiles.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`stable_sort`].
+pub unsafe fn nth_element<T: Sync>(
+    src: &mut [T],
+    nth: usize,
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Result<(), &'static str> {
+    if nth >= src.len() {
+        return Err("nth larger than source slice length");
+    }
+    permute_with(src, less, |perm, comp| {
+        ffi::hpx_nth_element_indices(perm, nth, comp).expect("nth is within the slice")
+    });
+    Ok(())
+}
+
+/// Copies the smallest `dest.len()` elements of `src`, as ordered by `less`,
+/// into `dest` in sorted order and returns how many were copied (the shorter
+/// of the two lengths).
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`stable_sort`].
+pub unsafe fn partial_sort_copy<T: Clone + Sync>(
+    src: &[T],
+    dest: &mut [T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> usize {
+    let mut indices = vec![0; dest.len().min(src.len())];
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    let len =
+        ffi::hpx_partial_sort_copy_indices(src.len(), &mut indices, &IndexCompare::new(&comp));
+    for (slot, &index) in dest.iter_mut().zip(&indices[..len]) {
+        *slot = src[index].clone();
+    }
+    len
+}
+
+/// Checks whether `src` is sorted with respect to `less`.
+pub fn is_sorted<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> bool {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    ffi::hpx_is_sorted_indices(src.len(), &IndexCompare::new(&comp))
+}
+
+/// Returns the length of the longest sorted prefix of `src`, `src.len()` if
+/// the whole slice is sorted with respect to `less`.
+pub fn is_sorted_until<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> usize {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    ffi::hpx_is_sorted_until_indices(src.len(), &IndexCompare::new(&comp))
+}
+
+// Element `index` of the concatenation of `src1` and `src2`, for the bindings
+// that see two ranges as one index space: indices below `src1.len()` refer to
+// `src1`, the rest to `src2`.
+fn concat_element<'a, T>(src1: &'a [T], src2: &'a [T], index: usize) -> &'a T {
+    if index < src1.len() {
+        &src1[index]
+    } else {
+        &src2[index - src1.len()]
+    }
+}
+
+/// Merges the sorted slices `src1` and `src2` into `dest`, which must be
+/// exactly as long as both together. Equal elements are taken from `src1`
+/// first. Writing into a caller provided buffer lets merge sorts reuse one
+/// scratch buffer across passes.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`stable_sort`]. HPX relies on
+/// it to hand every position of `dest` to exactly one worker thread.
+pub unsafe fn merge_by<T: Clone + Send + Sync>(
+    src1: &[T],
+    src2: &[T],
+    dest: &mut [T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Result<(), &'static str> {
+    if dest.len() != src1.len() + src2.len() {
+        return Err("destination length differs from the combined source length");
+    }
+    let element = |index: usize| concat_element(src1, src2, index);
+    let comp = |a: usize, b: usize| less(element(a), element(b));
+    let dest = SharedMutPtr(dest.as_mut_ptr());
+    let place = |pos: usize, src: usize| {
+        // SAFETY: the merge writes every position below `dest.len()` once.
+        unsafe { *dest.get().add(pos) = element(src).clone() }
+    };
+    ffi::hpx_merge_into(
+        src1.len(),
+        src2.len(),
+        &IndexCompare::new(&comp),
+        &IndexPlace::new(&place),
+    );
+    Ok(())
+}
+
+/// Merges the consecutive sorted ranges `src[..middle]` and `src[middle..]`
+/// in place, so that all of `src` is sorted with respect to `less`. The
+/// elements are moved through a scratch buffer of the same length, never
+/// cloned.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`merge_by`].
+pub unsafe fn inplace_merge<T: Send + Sync>(
+    src: &mut [T],
+    middle: usize,
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Result<(), &'static str> {
+    if middle > src.len() {
+        return Err("middle larger than source slice length");
+    }
+    let mut scratch: Vec<T> = Vec::with_capacity(src.len());
+    // The elements now live in `scratch`, whose length stays 0 so it never
+    // drops them; `src` only holds stale copies until every position has been
+    // written back. A panic in `less` aborts, so nothing sees them.
+    unsafe { std::ptr::copy_nonoverlapping(src.as_ptr(), scratch.as_mut_ptr(), src.len()) };
+    let moved = unsafe { std::slice::from_raw_parts(scratch.as_ptr(), src.len()) };
+    let (first, second) = moved.split_at(middle);
+    let comp = |a: usize, b: usize| {
+        less(
+            concat_element(first, second, a),
+            concat_element(first, second, b),
+        )
+    };
+    let dest = SharedMutPtr(src.as_mut_ptr());
+    let place = |pos: usize, index: usize| {
+        // SAFETY: each element of `scratch` is placed once, at a position no
+        // other thread writes, over a stale copy that must not be dropped.
+        unsafe { dest.get().add(pos).write(std::ptr::read(&moved[index])) }
+    };
+    ffi::hpx_merge_into(
+        first.len(),
+        second.len(),
+        &IndexCompare::new(&comp),
+        &IndexPlace::new(&place),
+    );
+    Ok(())
+}
+
+/// Returns the index of the first element of `src` satisfying `pred`.
+pub fn find_if<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> Option<usize> {
+    let pred_index = |index: usize| pred(&src[index]);
+    let index = ffi::hpx_find_if_indices(src.len(), &IndexPredicate::new(&pred_index));
+    found(index, src.len())
+}
+
+/// Returns the index of the first element of `src` not satisfying `pred`.
+pub fn find_if_not<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> Option<usize> {
+    let pred_index = |index: usize| pred(&src[index]);
+    let index = ffi::hpx_find_if_not_indices(src.len(), &IndexPredicate::new(&pred_index));
+    found(index, src.len())
+}
+
+/// Returns the start of the first occurrence of `needle` in `src`, comparing
+/// elements with `eq`. An empty `needle` is found at index 0.
+pub fn search<T: Sync, U: Sync>(
+    src: &[T],
+    needle: &[U],
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    let eq_indices = |a: usize, b: usize| eq(&src[a], &needle[b]);
+    let index = ffi::hpx_search_indices(src.len(), needle.len(), &IndexCompare::new(&eq_indices));
+    found(index, src.len())
+}
+
+/// Returns the start of the first run of `count` consecutive elements of
+/// `src` that are equal to `value` according to `eq`.
+///
+/// This has the semantics of `std::search_n`; `hpx::search_n` instead looks
+/// for a whole sequence within the first `count` elements, so the run is
+/// searched for as a needle of `count` copies of `value`.
+pub fn search_n<T: Sync, U: Sync>(
+    src: &[T],
+    count: usize,
+    value: &U,
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> Option<usize> {
+    if count == 0 {
+        return Some(0);
+    }
+    let eq_indices = |a: usize, _: usize| eq(&src[a], value);
+    let index = ffi::hpx_search_indices(src.len(), count, &IndexCompare::new(&eq_indices));
+    found(index, src.len())
+}
+
+/// Returns the start of the last occurrence of `needle` in `src`, comparing
+/// elements with `eq`. An empty `needle` is never found.
+pub fn find_end<T: Sync, U: Sync>(
+    src: &[T],
+    needle: &[U],
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    let eq_indices = |a: usize, b: usize| eq(&src[a], &needle[b]);
+    let index = ffi::hpx_find_end_indices(src.len(), needle.len(), &IndexCompare::new(&eq_indices));
+    found(index, src.len())
+}
+
+/// Returns the index of the first element of `src` that is equal to any of
+/// `candidates` according to `eq`.
+pub fn find_first_of<T: Sync, U: Sync>(
+    src: &[T],
+    candidates: &[U],
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> Option<usize> {
+    let eq_indices = |a: usize, b: usize| eq(&src[a], &candidates[b]);
+    let index = ffi::hpx_find_first_of_indices(
+        src.len(),
+        candidates.len(),
+        &IndexCompare::new(&eq_indices),
+    );
+    found(index, src.len())
+}
+
+/// Returns the index of the first element of `src` that is equal to the one
+/// following it according to `eq`.
+pub fn adjacent_find<T: Sync>(src: &[T], eq: impl Fn(&T, &T) -> bool + Sync) -> Option<usize> {
+    let eq_indices = |a: usize, b: usize| eq(&src[a], &src[b]);
+    let index = ffi::hpx_adjacent_find_indices(src.len(), &IndexCompare::new(&eq_indices));
+    found(index, src.len())
+}
+
+/// Returns the positions in `src1` and `src2` of the first pair of elements
+/// that are not equal according to `eq`, or `None` if the slices are equal.
+/// If one slice is a prefix of the other, the positions are its length.
+pub fn mismatch<T: Sync, U: Sync>(
+    src1: &[T],
+    src2: &[U],
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> Option<(usize, usize)> {
+    let eq_indices = |a: usize, b: usize| eq(&src1[a], &src2[b]);
+    let index = ffi::hpx_mismatch_indices(src1.len(), src2.len(), &IndexCompare::new(&eq_indices));
+    if index == src1.len() && index == src2.len() {
+        None
+    } else {
+        Some((index, index))
+    }
+}
+
+/// Checks whether `src` begins with `prefix`, comparing elements with `eq`.
+pub fn starts_with<T: Sync, U: Sync>(
+    src: &[T],
+    prefix: &[U],
+    eq: impl Fn(&T, &U) -> bool + Sync,
+) -> bool {
+    let eq_indices = |a: usize, b: usize| eq(&src[a], &prefix[b]);
+    ffi::hpx_starts_with_indices(src.len(), prefix.len(), &IndexCompare::new(&eq_indices))
+}
+
+/// Checks whether every element of `src` satisfies `pred`; true for an empty
+/// slice.
+pub fn all_of<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> bool {
+    let pred_index = |index: usize| pred(&src[index]);
+    ffi::hpx_all_of_indices(src.len(), &IndexPredicate::new(&pred_index))
+}
+
+/// Checks whether at least one element of `src` satisfies `pred`.
+pub fn any_of<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> bool {
+    let pred_index = |index: usize| pred(&src[index]);
+    ffi::hpx_any_of_indices(src.len(), &IndexPredicate::new(&pred_index))
+}
+
+/// Checks whether no element of `src` satisfies `pred`; true for an empty
+/// slice.
+pub fn none_of<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> bool {
+    let pred_index = |index: usize| pred(&src[index]);
+    ffi::hpx_none_of_indices(src.len(), &IndexPredicate::new(&pred_index))
+}
+
+/// Returns the index of the first smallest element of `src`.
+pub fn min_element<T: PartialOrd + Sync>(src: &[T]) -> Option<usize> {
+    min_element_by(src, |a, b| a < b)
+}
+
+/// Returns the index of the first smallest element of `src` as ordered by
+/// `less`.
+pub fn min_element_by<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> Option<usize> {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    let index = ffi::hpx_min_element_indices(src.len(), &IndexCompare::new(&comp));
+    found(index, src.len())
+}
+
+/// Returns the index of the first largest element of `src`.
+pub fn max_element<T: PartialOrd + Sync>(src: &[T]) -> Option<usize> {
+    max_element_by(src, |a, b| a < b)
+}
+
+/// Returns the index of the first largest element of `src` as ordered by
+/// `less`.
+pub fn max_element_by<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> Option<usize> {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    let index = ffi::hpx_max_element_indices(src.len(), &IndexCompare::new(&comp));
+    found(index, src.len())
+}
+
+/// Returns the indices of the first smallest and the last largest element of
+/// `src`, in a single pass.
+pub fn minmax_element<T: PartialOrd + Sync>(src: &[T]) -> Option<(usize, usize)> {
+    minmax_element_by(src, |a, b| a < b)
+}
+
+/// Like [`minmax_element`], with the elements ordered by `less`.
+pub fn minmax_element_by<T: Sync>(
+    src: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Option<(usize, usize)> {
+    if src.is_empty() {
+        return None;
+    }
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    let (mut min, mut max) = (0, 0);
+    ffi::hpx_minmax_element_indices(src.len(), &IndexCompare::new(&comp), &mut min, &mut max);
+    Some((min, max))
+}
+
+/// Parallel counterparts of the `[T]::sort_*` methods, taking the same
+/// closures. The `par_` prefix keeps them from being shadowed by the inherent
+/// slice methods.
+///
+/// Unlike the slice methods these sort through C++, so they are `unsafe`: the
+/// comparator (or the `Ord` implementation of the key) must be a total order,
+/// see [`stable_sort`]. The `sort_bench` example in `hpx-examples` compares
+/// them against `[T]::sort_by`; sequential sorting stays faster for small or
+/// cheap to compare slices.
+pub trait ParallelSort<T> {
+    /// Stable sort with a comparator, like `[T]::sort_by`.
+    ///
+    /// # Safety
+    ///
+    /// `compare` must be a total order on the elements.
+    unsafe fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync;
+
+    /// Stable sort by an extracted key, like `[T]::sort_by_key`. The key is
+    /// recomputed on every comparison.
+    ///
+    /// # Safety
+    ///
+    /// The `Ord` implementation of `K` must be a total order.
+    unsafe fn par_sort_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: Fn(&T) -> K + Sync;
+
+    /// Unstable sort with a comparator, like `[T]::sort_unstable_by`.
+    ///
+    /// # Safety
+    ///
+    /// `compare` must be a total order on the elements.
+    unsafe fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync;
+}
+
+impl<T: Sync> ParallelSort<T> for [T] {
+    unsafe fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        permute_with(
+            self,
+            |a, b| compare(a, b) == Ordering::Less,
+            ffi::hpx_stable_sort_indices,
+        );
+    }
+
+    unsafe fn par_sort_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: Fn(&T) -> K + Sync,
+    {
+        permute_with(self, |a, b| f(a) < f(b), ffi::hpx_stable_sort_indices);
+    }
+
+    unsafe fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        permute_with(
+            self,
+            |a, b| compare(a, b) == Ordering::Less,
+            ffi::hpx_sort_indices,
+        );
+    }
+}
+
+/// Replaces every element of `src` equal to `old_value` with `new_value`.
+pub fn replace<T: NumericElement>(src: &mut [T], old_value: T, new_value: T) {
+    T::replace(src, old_value, new_value);
+}
+
+/// Replaces every element of `src` satisfying `pred` with a clone of
+/// `new_value`.
+pub fn replace_if<T: Clone + Send + Sync>(
+    src: &mut [T],
+    pred: impl Fn(&T) -> bool + Sync,
+    new_value: T,
+) {
+    let ptr = SharedMutPtr(src.as_mut_ptr());
+    let replace_index = |index: usize| {
+        // SAFETY: `hpx_for_each_index` visits every index below `len` exactly
+        // once, so no element is accessed by two threads.
+        let element = unsafe { &mut *ptr.get().add(index) };
+        if pred(element) {
+            *element = new_value.clone();
+        }
+    };
+    ffi::hpx_for_each_index(src.len(), &IndexFunction::new(&replace_index));
+}
+
+/// Copy of `src` with every element equal to `old_value` replaced by
+/// `new_value`.
+pub fn replace_copy<T: NumericElement>(src: &[T], old_value: T, new_value: T) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::replace_copy(src, &mut dest, old_value, new_value)
+        .expect("destination is as long as the source");
+    dest
+}
+
+/// Moves the elements of `src` not equal to `value` to the front, keeping
+/// their order, and returns how many there are. The elements past the
+/// returned length are unspecified; see [`erase`] to drop them from a `Vec`.
+pub fn remove<T: NumericElement>(src: &mut [T], value: T) -> usize {
+    T::remove(src, value)
+}
+
+/// Like [`remove`], for the elements satisfying `pred`.
+pub fn remove_if<T: Sync>(src: &mut [T], pred: impl Fn(&T) -> bool + Sync) -> usize {
+    let mut kept: Vec<usize> = (0..src.len()).collect();
+    let len = {
+        let elements: &[T] = src;
+        let pred_index = |index: usize| pred(&elements[index]);
+        ffi::hpx_remove_if_indices(&mut kept, &IndexPredicate::new(&pred_index))
+    };
+    // The kept indices are increasing and `kept[i] >= i`, so swapping each
+    // element forward never disturbs one that is still to be moved.
+    for (dest, &index) in kept[..len].iter().enumerate() {
+        src.swap(dest, index);
+    }
+    len
+}
+
+/// Removes every element equal to `value` from `vec` and returns how many
+/// were removed.
+pub fn erase<T: NumericElement>(vec: &mut Vec<T>, value: T) -> usize {
+    let len = remove(vec, value);
+    let removed = vec.len() - len;
+    vec.truncate(len);
+    removed
+}
+
+/// Removes every element satisfying `pred` from `vec` and returns how many
+/// were removed.
+pub fn erase_if<T: Sync>(vec: &mut Vec<T>, pred: impl Fn(&T) -> bool + Sync) -> usize {
+    let len = remove_if(vec, pred);
+    let removed = vec.len() - len;
+    vec.truncate(len);
+    removed
+}
+
+/// Collapses every run of equal consecutive elements of `src` to its first
+/// element, moving the results to the front, and returns how many there are.
+pub fn unique<T: NumericElement>(src: &mut [T]) -> usize {
+    T::unique(src)
+}
+
+/// Copy of `src` with every run of equal consecutive elements collapsed to
+/// its first element.
+pub fn unique_copy<T: NumericElement>(src: &[T]) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    let len = T::unique_copy(src, &mut dest).expect("destination is as long as the source");
+    dest.truncate(len);
+    dest
+}
+
+/// Reverses the order of the elements of `src`.
+pub fn reverse<T: NumericElement>(src: &mut [T]) {
+    T::reverse(src);
+}
+
+/// Rotates `src` so that `src[middle]` becomes the first element.
+pub fn rotate<T: NumericElement>(src: &mut [T], middle: usize) -> Result<(), &'static str> {
+    T::rotate(src, middle).map_err(|_| "middle larger than source slice length")
+}
+
+/// Copy of `src` rotated so that `src[middle]` is the first element.
+pub fn rotate_copy<T: NumericElement>(src: &[T], middle: usize) -> Result<Vec<T>, &'static str> {
+    let mut dest = vec![T::default(); src.len()];
+    T::rotate_copy(src, middle, &mut dest).map_err(|_| "middle larger than source slice length")?;
+    Ok(dest)
+}
+
+/// Moves the elements of `src` `n` positions towards the front. The values of
+/// the last `n` elements are unspecified afterwards; nothing happens if `n`
+/// is at least `src.len()`.
+pub fn shift_left<T: NumericElement>(src: &mut [T], n: usize) {
+    T::shift_left(src, n);
+}
+
+/// Moves the elements of `src` `n` positions towards the back. The values of
+/// the first `n` elements are unspecified afterwards; nothing happens if `n`
+/// is at least `src.len()`.
+pub fn shift_right<T: NumericElement>(src: &mut [T], n: usize) {
+    T::shift_right(src, n);
+}
+
+// Partition `src` by running `partition` (one of the index based partition
+// bindings) on its indices and then moving the elements into place.
+fn partition_with<T: Sync>(
+    src: &mut [T],
+    pred: impl Fn(&T) -> bool + Sync,
+    partition: fn(&mut [usize], &IndexPredicate) -> usize,
+) -> usize {
+    let mut perm: Vec<usize> = (0..src.len()).collect();
+    let split = {
+        let elements: &[T] = src;
+        let pred_index = |index: usize| pred(&elements[index]);
+        partition(&mut perm, &IndexPredicate::new(&pred_index))
+    };
+    apply_permutation(src, &mut perm);
+    split
+}
+
+/// Moves the elements of `src` satisfying `pred` before the ones that do not
+/// and returns the index of the first element of the second group. The
+/// relative order within the groups is not preserved.
+pub fn partition<T: Sync>(src: &mut [T], pred: impl Fn(&T) -> bool + Sync) -> usize {
+    partition_with(src, pred, ffi::hpx_partition_indices)
+}
+
+/// Like [`partition`], but keeps the relative order within both groups.
+pub fn stable_partition<T: Sync>(src: &mut [T], pred: impl Fn(&T) -> bool + Sync) -> usize {
+    partition_with(src, pred, ffi::hpx_stable_partition_indices)
+}
+
+/// Splits `src` into clones of the elements that satisfy `pred` and of the
+/// ones that do not, keeping their relative order.
+pub fn partition_copy<T: Clone + Sync>(
+    src: &[T],
+    pred: impl Fn(&T) -> bool + Sync,
+) -> (Vec<T>, Vec<T>) {
+    let mut indices_true = vec![0; src.len()];
+    let mut indices_false = vec![0; src.len()];
+    let pred_index = |index: usize| pred(&src[index]);
+    let len = ffi::hpx_partition_copy_indices(
+        src.len(),
+        &mut indices_true,
+        &mut indices_false,
+        &IndexPredicate::new(&pred_index),
+    )
+    .expect("destinations are as long as the source");
+    let collect = |indices: &[usize]| indices.iter().map(|&index| src[index].clone()).collect();
+    (
+        collect(&indices_true[..len]),
+        collect(&indices_false[..src.len() - len]),
+    )
+}
+
+/// Checks whether all elements of `src` satisfying `pred` come before all the
+/// ones that do not.
+pub fn is_partitioned<T: Sync>(src: &[T], pred: impl Fn(&T) -> bool + Sync) -> bool {
+    let pred_index = |index: usize| pred(&src[index]);
+    ffi::hpx_is_partitioned_indices(src.len(), &IndexPredicate::new(&pred_index))
+}
+
+// Run one of the sorted range set bindings on `src1` and `src2` and collect
+// the selected elements.
+fn set_operation<T: Clone + Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+    operation: fn(usize, usize, &mut [usize], &IndexCompare) -> Result<usize, cxx::Exception>,
+) -> Vec<T> {
+    let element = |index: usize| concat_element(src1, src2, index);
+    let mut indices = vec![0; src1.len() + src2.len()];
+    let comp = |a: usize, b: usize| less(element(a), element(b));
+    let len = operation(
+        src1.len(),
+        src2.len(),
+        &mut indices,
+        &IndexCompare::new(&comp),
+    )
+    .expect("destination is as long as both sources");
+    indices[..len]
+        .iter()
+        .map(|&index| element(index).clone())
+        .collect()
+}
+
+/// Union of the sorted slices `src1` and `src2`. An element present `m` times
+/// in `src1` and `n` times in `src2` appears `max(m, n)` times.
+pub fn set_union<T: Clone + PartialOrd + Sync>(src1: &[T], src2: &[T]) -> Vec<T> {
+    set_union_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`set_union`], for slices sorted by `less`.
+pub fn set_union_by<T: Clone + Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Vec<T> {
+    set_operation(src1, src2, less, ffi::hpx_set_union_indices)
+}
+
+/// Elements of the sorted slice `src1` that are also in the sorted slice
+/// `src2`, `min(m, n)` times for an element present `m` and `n` times.
+pub fn set_intersection<T: Clone + PartialOrd + Sync>(src1: &[T], src2: &[T]) -> Vec<T> {
+    set_intersection_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`set_intersection`], for slices sorted by `less`.
+pub fn set_intersection_by<T: Clone + Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Vec<T> {
+    set_operation(src1, src2, less, ffi::hpx_set_intersection_indices)
+}
+
+/// Elements of the sorted slice `src1` that are not in the sorted slice
+/// `src2`, `m - n` times for an element present `m` and `n` times.
+pub fn set_difference<T: Clone + PartialOrd + Sync>(src1: &[T], src2: &[T]) -> Vec<T> {
+    set_difference_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`set_difference`], for slices sorted by `less`.
+pub fn set_difference_by<T: Clone + Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Vec<T> {
+    set_operation(src1, src2, less, ffi::hpx_set_difference_indices)
+}
+
+/// Elements found in exactly one of the sorted slices `src1` and `src2`, in
+/// sorted order.
+pub fn set_symmetric_difference<T: Clone + PartialOrd + Sync>(src1: &[T], src2: &[T]) -> Vec<T> {
+    set_symmetric_difference_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`set_symmetric_difference`], for slices sorted by `less`.
+pub fn set_symmetric_difference_by<T: Clone + Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Vec<T> {
+    set_operation(src1, src2, less, ffi::hpx_set_symmetric_difference_indices)
+}
+
+/// Checks whether every element of the sorted slice `src2` is contained in
+/// the sorted slice `src1`.
+pub fn includes<T: PartialOrd + Sync>(src1: &[T], src2: &[T]) -> bool {
+    includes_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`includes`], for slices sorted by `less`.
+pub fn includes_by<T: Sync>(src1: &[T], src2: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> bool {
+    let element = |index: usize| concat_element(src1, src2, index);
+    let comp = |a: usize, b: usize| less(element(a), element(b));
+    ffi::hpx_includes_indices(src1.len(), src2.len(), &IndexCompare::new(&comp))
+}
+
+/// Sets every element of `dest` to `f(index)`, calling `f` in parallel.
+///
+/// Unlike `hpx::generate` the generator sees the index it fills, so elements
+/// can be computed independently, e.g. a per chunk seed or a linear ramp.
+pub fn generate<T: Send>(dest: &mut [T], f: impl Fn(usize) -> T + Sync) {
+    let len = dest.len();
+    let dest = SharedMutPtr(dest.as_mut_ptr());
+    let write = |index: usize| {
+        // SAFETY: `hpx_for_each_index` visits every index below `len` exactly
+        // once, so no element is written by two threads.
+        unsafe { *dest.get().add(index) = f(index) }
+    };
+    ffi::hpx_for_each_index(len, &IndexFunction::new(&write));
+}
+
+/// Like [`generate`], for the first `n` elements of `dest`.
+pub fn generate_n<T: Send>(
+    dest: &mut [T],
+    n: usize,
+    f: impl Fn(usize) -> T + Sync,
+) -> Result<(), &'static str> {
+    if n > dest.len() {
+        return Err("n larger than destination slice length");
+    }
+    generate(&mut dest[..n], f);
+    Ok(())
+}
+
+/// Fills `dest` with `value`, `value + 1`, `value + 2`, ... Integers wrap
+/// around on overflow, as with `wrapping_add`.
+pub fn iota<T: NumericElement>(dest: &mut [T], value: T) {
+    T::iota(dest, value)
+}
+
+/// The first element of `src` followed by the difference of each element and
+/// its predecessor.
+pub fn adjacent_difference<T: NumericElement + Sub<Output = T>>(src: &[T]) -> Vec<T> {
+    adjacent_difference_by(src, |current, previous| current - previous)
+}
+
+/// Like [`adjacent_difference`], computing `op(current, previous)` instead of
+/// the difference.
+pub fn adjacent_difference_by<T: NumericElement>(
+    src: &[T],
+    op: impl Fn(T, T) -> T + Sync,
+) -> Vec<T> {
+    let mut dest = vec![T::default(); src.len()];
+    T::adjacent_difference(src, &mut dest, &ValueOp::new(&op))
+        .expect("destination is as long as the source");
+    dest
+}
+
+/// Rearranges `src` in parallel into a heap with respect to `less`: no element
+/// is less than either of its children `2 * i + 1` and `2 * i + 2`, so the
+/// greatest element comes first, as in `std::collections::BinaryHeap`.
+/// [`binary_heap_from_vec`] builds a `BinaryHeap` this way.
+///
+/// # Safety
+///
+/// `less` must be a strict weak ordering, see [`stable_sort`].
+pub unsafe fn make_heap<T: Sync>(src: &mut [T], less: impl Fn(&T, &T) -> bool + Sync) {
+    permute_with(src, less, ffi::hpx_make_heap_indices);
+}
+
+/// Checks whether `src` is a heap with respect to `less`, see [`make_heap`].
+pub fn is_heap<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> bool {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    ffi::hpx_is_heap_indices(src.len(), &IndexCompare::new(&comp))
+}
+
+/// Returns the length of the longest prefix of `src` that is a heap with
+/// respect to `less`, `src.len()` if the whole slice is one.
+pub fn is_heap_until<T: Sync>(src: &[T], less: impl Fn(&T, &T) -> bool + Sync) -> usize {
+    let comp = |a: usize, b: usize| less(&src[a], &src[b]);
+    ffi::hpx_is_heap_until_indices(src.len(), &IndexCompare::new(&comp))
+}
+
+/// Builds a `BinaryHeap` from `vec`, heapifying it in parallel first.
+///
+/// `BinaryHeap::from` still walks the vector once, but as it is already a heap
+/// under the same `Ord` no element moves and the walk is a cheap sequential
+/// check.
+///
+/// # Safety
+///
+/// The `Ord` implementation of `T` must be a total order, see [`stable_sort`].
+/// Only the parallel [`make_heap`] relies on it; `BinaryHeap` itself stays
+/// sound with any `Ord`.
+pub unsafe fn binary_heap_from_vec<T: Ord + Sync>(mut vec: Vec<T>) -> BinaryHeap<T> {
+    make_heap(&mut vec, |a, b| a < b);
+    BinaryHeap::from(vec)
+}
+
+/// Checks whether `src1` and `src2` have the same length and `eq` holds for
+/// every pair of elements at the same position, e.g. to compare floating
+/// point results within a tolerance. [`mismatch`] locates the first
+/// difference.
+pub fn equal<T: Sync, U: Sync>(src1: &[T], src2: &[U], eq: impl Fn(&T, &U) -> bool + Sync) -> bool {
+    let eq_indices = |a: usize, b: usize| eq(&src1[a], &src2[b]);
+    ffi::hpx_equal_indices(src1.len(), src2.len(), &IndexCompare::new(&eq_indices))
+}
+
+/// Compares `src1` and `src2` lexicographically.
+pub fn lexicographical_compare<T: PartialOrd + Sync>(src1: &[T], src2: &[T]) -> Ordering {
+    lexicographical_compare_by(src1, src2, |a, b| a < b)
+}
+
+/// Like [`lexicographical_compare`], ordering elements by `less`.
+pub fn lexicographical_compare_by<T: Sync>(
+    src1: &[T],
+    src2: &[T],
+    less: impl Fn(&T, &T) -> bool + Sync,
+) -> Ordering {
+    let element = |index: usize| concat_element(src1, src2, index);
+    let comp = |a: usize, b: usize| less(element(a), element(b));
+    let comp = IndexCompare::new(&comp);
+    let (len1, len2) = (src1.len(), src2.len());
+    // HPX only answers "less than", so a second pass with the ranges swapped
+    // tells greater from equal.
+    if ffi::hpx_lexicographical_compare_indices(0, len1, len1, len2, &comp) {
+        Ordering::Less
+    } else if ffi::hpx_lexicographical_compare_indices(len1, len2, 0, len1, &comp) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Copies `src` into a new `Vec` whose storage is first written by the HPX
+/// worker threads, rather than zeroed on the calling thread and overwritten.
+pub fn uninitialized_copy<T: NumericElement>(src: &[T]) -> Vec<T> {
+    let mut dest = Vec::with_capacity(src.len());
+    // SAFETY: `dest` has room for `src.len()` elements, all of which are
+    // written before the length is set.
+    unsafe {
+        T::uninitialized_copy(src, dest.as_mut_ptr());
+        dest.set_len(src.len());
+    }
+    dest
+}
+
+/// A `Vec` of `len` copies of `value`, filled in parallel like
+/// [`uninitialized_copy`].
+pub fn uninitialized_fill<T: NumericElement>(len: usize, value: T) -> Vec<T> {
+    let mut dest = Vec::with_capacity(len);
+    // SAFETY: as in `uninitialized_copy`.
+    unsafe {
+        T::uninitialized_fill(dest.as_mut_ptr(), len, value);
+        dest.set_len(len);
+    }
+    dest
+}
+
+/// Like [`generate`], building a new `Vec` of `len` elements whose storage is
+/// first written by the HPX worker threads.
+pub fn generate_vec<T: Send>(len: usize, f: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    let mut dest: Vec<T> = Vec::with_capacity(len);
+    let ptr = SharedMutPtr(dest.as_mut_ptr());
+    let write = |index: usize| {
+        // SAFETY: every index below `len` is written exactly once and nothing
+        // is read before the length is set; a panic in `f` aborts.
+        unsafe { ptr.get().add(index).write(f(index)) }
+    };
+    ffi::hpx_for_each_index(len, &IndexFunction::new(&write));
+    // SAFETY: all `len` elements were written above.
+    unsafe { dest.set_len(len) };
+    dest
+}
+
+/// A `Vec` of `len` default values, constructed in parallel on the HPX worker
+/// threads.
+///
+/// `hpx::uninitialized_default_construct` runs C++ constructors and leaves
+/// numbers indeterminate, so this and [`uninitialized_move`] and [`destroy`]
+/// run the Rust operations through `hpx::for_each` instead.
+pub fn uninitialized_default_construct<T: Default + Send>(len: usize) -> Vec<T> {
+    generate_vec(len, |_| T::default())
+}
+
+/// Moves the elements of `src` into a new `Vec`, in parallel, so its storage
+/// is first touched by the HPX worker threads.
+pub fn uninitialized_move<T: Send>(mut src: Vec<T>) -> Vec<T> {
+    let len = src.len();
+    // SAFETY: the elements are moved out below and only the allocation of
+    // `src` is freed when it goes out of scope.
+    unsafe { src.set_len(0) };
+    let ptr = SharedMutPtr(src.as_mut_ptr());
+    // SAFETY: each of the `len` initialized elements is read exactly once.
+    generate_vec(len, |index| unsafe { ptr.get().add(index).read() })
+}
+
+/// Drops the elements of `vec` in parallel, then frees its storage.
+pub fn destroy<T: Send>(mut vec: Vec<T>) {
+    let len = vec.len();
+    // SAFETY: as in `uninitialized_move`, the elements are dropped below.
+    unsafe { vec.set_len(0) };
+    let ptr = SharedMutPtr(vec.as_mut_ptr());
+    let drop_element = |index: usize| {
+        // SAFETY: each of the `len` initialized elements is dropped once.
+        unsafe { ptr.get().add(index).drop_in_place() }
+    };
+    ffi::hpx_for_each_index(len, &IndexFunction::new(&drop_element));
+}
+
+/// Applies `conv` to every element of `src` and reduces the results with `op`,
+/// starting from `init`. `op` must be associative, see [`inclusive_scan`].
+pub fn transform_reduce<T: Sync, R: NumericElement>(
+    src: &[T],
+    init: R,
+    op: impl Fn(R, R) -> R + Sync,
+    conv: impl Fn(&T) -> R + Sync,
+) -> R {
+    let conv_index = |index: usize| conv(&src[index]);
+    R::transform_reduce(
+        src.len(),
+        init,
+        &ValueOp::new(&op),
+        &IndexValue::new(&conv_index),
+    )
+}
+
+/// Returns the topology of the machine. Unlike most bindings this does not
+/// need the runtime, so it can size pools and buffers before [`ffi::init`].
+pub fn topology() -> ffi::Topology {
+    let mut topology = ffi::Topology::default();
+    ffi::hpx_get_topology(&mut topology);
+    topology
+}
+
+/// Returns the ids of the PUs the calling thread may run on.
+pub fn thread_affinity() -> Vec<usize> {
+    ffi::hpx_get_cpubind_pus()
+}
+
+// ================================================================================================
+// Tests (to be shifted to systests crate within hpx-rs workspace)
+// ================================================================================================
+#[cfg(test)]
+mod tests {
+    use super::ffi;
+    use crate::{
+        adjacent_difference, adjacent_difference_by, adjacent_find, all_of, any_of,
+        binary_heap_from_vec, copy_if_divisiblileityby3, copy_n, copy_vector, count, create_c_args,
+        destroy, equal, erase, erase_if, exclusive_scan, exclusive_scan_inplace, find, find_end,
+        find_first_of, find_if, find_if_not, generate, generate_n, includes, includes_by,
+        inclusive_scan, inclusive_scan_inplace, inplace_merge, iota, is_heap, is_heap_until,
+        is_partitioned, is_sorted, is_sorted_until, lexicographical_compare,
+        lexicographical_compare_by, make_heap, max_element, max_element_by, merge_by, min_element,
+        min_element_by, minmax_element, minmax_element_by, mismatch, none_of, nth_element,
+        partial_sort_copy, partition, partition_copy, reduce_by_key, remove, remove_if, replace,
+        replace_copy, replace_if, reverse, rotate, rotate_copy, search, search_n, set_difference,
+        set_difference_by, set_intersection, set_intersection_by, set_symmetric_difference,
+        set_symmetric_difference_by, set_union, set_union_by, shift_left, shift_right, sort_by_key,
+        stable_partition, stable_sort, starts_with, thread_affinity, topology,
+        transform_exclusive_scan, transform_exclusive_scan_inplace, transform_inclusive_scan,
+        transform_inclusive_scan_inplace, transform_reduce, uninitialized_copy,
+        uninitialized_default_construct, uninitialized_fill, uninitialized_move, unique,
+        unique_copy, IndexCompare, IndexPredicate, ParallelSort, ValueOp,
+    };
+    use serial_test::serial;
+    use std::cmp::Ordering;
+    use std::ffi::CString;
+    use std::os::raw::c_char;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+    use std::sync::OnceLock;
+
+    #[test]
+    #[serial]
+    fn test_init_finalize() {
+        let (argc, mut argv) = create_c_args(&["testing", "arg1", "arg2"]);
+
+        let dummy_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            println!("Dummy fn called");
+            // to exit hpx::init you are required to shutdown hpx runtime
+            ffi::finalize();
+            0
+        };
+
+        unsafe {
+            let result = ffi::init(dummy_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_copy() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_copy"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let src = vec![1, 2, 3, 4, 5];
+            let result = copy_vector(&src);
+            assert_eq!(src, result);
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_copy_range() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_copy"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let src = vec![1, 2, 3, 4, 5];
+            let result = copy_vector(&src[0..3]);
+            assert_eq!(&src[0..3], &result);
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_copy_n() {
+        let (argc, mut argv) = create_c_args(&["test_copy_n"]);
+
+        let test_func = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let src = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            match copy_n(&src, 5) {
+                Ok(result) => assert_eq!(result, vec![1, 2, 3, 4, 5]),
+                Err(e) => panic!("Unexpected error: {}", e),
+            }
+
+            match copy_n(&src, 15) {
+                // expecting error
+                Ok(_) => panic!("Expected error, but got Ok"),
+                Err(e) => assert_eq!(e, "count larger than source slice length"),
+            }
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(test_func, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_copy_if() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_copy_if"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let src = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+            let result = copy_if_divisiblileityby3(&src);
+            assert_eq!(result, vec![0, 3, 6, 9, 12]);
+
+            let mut short = vec![0; 3];
+            assert!(ffi::hpx_copy_if(&src, &mut short, |x| x % 3 == 0).is_err());
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_count() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_count"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let vec = vec![1, 2, 3, 2, 4, 2, 5, 2];
+            let result = count(&vec, 2);
+            assert_eq!(result, 4);
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_count_if() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_count_if"]);
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let vec = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            let result_even = ffi::hpx_count_if(&vec, |x| x % 2 == 0);
+            assert_eq!(result_even, 5);
+            let result_greater_than_5 = ffi::hpx_count_if(&vec, |x| x > 5);
+            assert_eq!(result_greater_than_5, 5);
+            let is_prime = |n: i32| {
+                if n <= 1 {
+                    return false;
+                }
+                for i in 2..=(n as f64).sqrt() as i32 {
+                    if n % i == 0 {
+                        return false;
+                    }
+                }
+                true
+            };
+            let result_prime = ffi::hpx_count_if(&vec, is_prime);
+            assert_eq!(result_prime, 4);
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_ends_with() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_ends_with"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v1 = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            let v2 = vec![8, 9, 10];
+            let v3 = vec![7, 8, 9];
+            let v4 = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            let v5: Vec<i32> = vec![];
+
+            // passing vectors
+            assert!(ffi::hpx_ends_with(&v1, &v2));
+            assert!(!ffi::hpx_ends_with(&v1, &v3));
+            assert!(ffi::hpx_ends_with(&v1, &v4));
+            assert!(ffi::hpx_ends_with(&v1, &v5));
+            assert!(ffi::hpx_ends_with(&v5, &v5));
+
+            // passing slices
+            assert!(ffi::hpx_ends_with(&v1[5..], &v2));
+            assert!(ffi::hpx_ends_with(&v1[..], &v1[8..]));
+            assert!(!ffi::hpx_ends_with(&v1[..5], &v2));
+            assert!(ffi::hpx_ends_with(&v1[..5], &v1[3..5]));
+
+            assert!(ffi::hpx_ends_with(&v1, &[]));
+            assert!(ffi::hpx_ends_with(&[], &[]));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_equal() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_equal"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v1 = vec![1, 2, 3, 4, 5];
+            let v2 = vec![1, 2, 3, 4, 5];
+            let v3 = vec![1, 2, 3, 4, 6];
+            let v4 = vec![1, 2, 3, 4];
+            let v5 = vec![0, 1, 2, 3, 4, 5, 6];
+
+            // passing vectors
+            assert!(ffi::hpx_equal(&v1, &v2));
+            assert!(!ffi::hpx_equal(&v1, &v3));
+            assert!(!ffi::hpx_equal(&v1, &v4));
+
+            // passing slices
+            assert!(ffi::hpx_equal(&v1[..], &v2[..]));
+            assert!(ffi::hpx_equal(&v1[1..4], &v2[1..4]));
+            assert!(ffi::hpx_equal(&v1[..3], &v4[..3]));
+            assert!(ffi::hpx_equal(&v1[..], &v5[1..6]));
+
+            assert!(ffi::hpx_equal(&v1, &v5[1..6]));
+            assert!(!ffi::hpx_equal(&v1[..4], &v3));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_fill() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_fill"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![0; 10];
+            ffi::hpx_fill(&mut v, 42);
+            assert!(v.iter().all(|&x| x == 42));
+
+            let mut v2 = vec![0; 1_000_000]; // testing on a long vector
+            ffi::hpx_fill(&mut v2, 7);
+            assert!(v2.iter().all(|&x| x == 7));
+
+            let mut v3: Vec<i32> = Vec::new();
+            ffi::hpx_fill(&mut v3, 100);
+            assert!(v3.is_empty());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_find() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_find"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+            let result = find(&v, 5); // finding existing value
+            assert_eq!(result, Some(4));
+
+            let result = find(&v, 11); // finding non-existing value
+            assert_eq!(result, None);
+
+            let result = find(&v, 1);
+            assert_eq!(result, Some(0));
+
+            let result = find(&v, 10);
+            assert_eq!(result, Some(9));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_sort() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_sort"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut src = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            ffi::hpx_sort(&mut src);
+            assert_eq!(src, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_sort_comp() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_sort_comp"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            ffi::hpx_sort_comp(&mut v, |a, b| a > b); // sorting in descending order
+            assert_eq!(v, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+            // sorting even numbers before odd numbers
+            let mut v2 = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            ffi::hpx_sort_comp(
+                &mut v2,
+                |a, b| {
+                    if a % 2 == b % 2 {
+                        a < b
+                    } else {
+                        a % 2 == 0
+                    }
+                },
+            );
+            assert_eq!(v2, vec![2, 4, 6, 8, 1, 3, 5, 7, 9]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_merge() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_merge"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v1 = vec![1, 3, 5, 7, 9, 20, 100];
+            let v2 = vec![2, 4, 6, 8, 10, 97];
+            let mut dest = vec![0; v1.len() + v2.len()];
+            ffi::hpx_merge(&v1, &v2, &mut dest).unwrap();
+            assert_eq!(dest, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 97, 100]);
+
+            assert!(ffi::hpx_merge(&v1, &v2, &mut []).is_err());
+            let mut short = vec![0; v1.len()];
+            assert!(ffi::hpx_merge(&v1, &v2, &mut short).is_err());
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_partial_sort() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_partial_sort"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut vec = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            let last = 4;
+            println!("Before partial sort: {:?}", vec);
+
+            ffi::hpx_partial_sort(&mut vec, last);
+            println!("After partial sort: {:?}", vec);
+
+            // If first -> last elements are sorted
+            assert!(vec[..last].windows(2).all(|w| w[0] <= w[1]));
+
+            // If ele of sorted part <=  ele of unsorted part
+            assert!(vec[..last]
+                .iter()
+                .all(|&x| vec[last..].iter().all(|&y| x <= y)));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_partial_sort_comp() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_partial_sort_comp"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut vec = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            let last = 4;
+            println!("Before partial sort: {:?}", vec);
+
+            ffi::hpx_partial_sort_comp(&mut vec, last, |a, b| b < a);
+            println!("After partial sort: {:?}", vec);
+
+            // If first -> last elements are sorted dec
+            assert!(vec[..last].windows(2).all(|w| w[0] >= w[1]));
+
+            // If ele of sorted part >= ele of unsorted part
+            assert!(vec[..last]
+                .iter()
+                .all(|&x| vec[last..].iter().all(|&y| x >= y)));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_inclusive_scan() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_inclusive_scan"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1, 2, 3, 4, 5];
+            assert_eq!(inclusive_scan(&v, |a, b| a + b), vec![1, 3, 6, 10, 15]);
+            assert_eq!(inclusive_scan(&v, |a, b| a.max(b)), vec![1, 2, 3, 4, 5]);
+            let modulus = 7;
+            assert_eq!(
+                inclusive_scan(&v, |a, b| (a + b) % modulus),
+                vec![1, 3, 6, 3, 1]
+            );
+
+            let counts: Vec<u64> = vec![3; 100_000]; // testing on a long vector
+            let offsets = inclusive_scan(&counts, |a, b| a + b);
+            assert!(offsets
+                .iter()
+                .enumerate()
+                .all(|(i, &x)| x == 3 * (i as u64 + 1)));
+
+            let mut f = vec![0.5, 1.5, 2.0];
+            inclusive_scan_inplace(&mut f, |a, b| a + b);
+            assert_eq!(f, vec![0.5, 2.0, 4.0]);
+
+            let empty: Vec<i64> = Vec::new();
+            assert!(inclusive_scan(&empty, |a, b| a + b).is_empty());
+
+            let mut short = vec![0; 2];
+            let add = |a, b| a + b;
+            assert!(ffi::hpx_inclusive_scan_i32(&v, &mut short, &ValueOp::new(&add)).is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_exclusive_scan() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_exclusive_scan"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1, 2, 3, 4, 5];
+            assert_eq!(exclusive_scan(&v, 0, |a, b| a + b), vec![0, 1, 3, 6, 10]);
+            assert_eq!(exclusive_scan(&v, 1, |a, b| a * b), vec![1, 1, 2, 6, 24]);
+
+            // bucket offsets from a histogram
+            let mut histogram: Vec<u64> = vec![4, 0, 2, 7];
+            exclusive_scan_inplace(&mut histogram, 0, |a, b| a + b);
+            assert_eq!(histogram, vec![0, 4, 4, 6]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_transform_inclusive_scan() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_transform_inclusive_scan"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v: Vec<i64> = vec![1, -2, 3, -4];
+            let result = transform_inclusive_scan(&v, |a, b| a + b, |x| x * x);
+            assert_eq!(result, vec![1, 5, 14, 30]);
+
+            let mut v2 = vec![1, -2, 3, -4];
+            transform_inclusive_scan_inplace(&mut v2, |a, b| a + b, |x: i32| x.abs());
+            assert_eq!(v2, vec![1, 3, 6, 10]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_transform_exclusive_scan() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_transform_exclusive_scan"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![5, 0, 3, 0, 8];
+            // offsets of the non-zero elements, as used for stream compaction
+            let flags = |x: i32| if x != 0 { 1 } else { 0 };
+            let result = transform_exclusive_scan(&v, 0, |a, b| a + b, flags);
+            assert_eq!(result, vec![0, 1, 1, 2, 2]);
+
+            let mut v2: Vec<f64> = vec![1.0, 2.0, 3.0];
+            transform_exclusive_scan_inplace(&mut v2, 10.0, |a, b| a + b, |x| 2.0 * x);
+            assert_eq!(v2, vec![10.0, 12.0, 16.0]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_reduce_by_key() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_reduce_by_key"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut keys = vec!["a", "a", "b", "c", "c", "c", "a"];
+            let mut values = vec![1, 2, 3, 4, 5, 6, 7];
+            let len = reduce_by_key(&mut keys, &mut values, |a, b| a == b, |a, b| a + b);
+            assert_eq!(len, Ok(4));
+            assert_eq!(&keys[..4], &["a", "b", "c", "a"]);
+            assert_eq!(&values[..4], &[3, 3, 15, 7]);
+
+            // custom equality: group by the integer part
+            let mut keys = vec![1.1, 1.9, 2.5, 3.0, 3.2];
+            let mut values: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+            let len = reduce_by_key(
+                &mut keys,
+                &mut values,
+                |a: &f64, b: &f64| a.floor() == b.floor(),
+                |a: &f64, b: &f64| a.max(*b),
+            );
+            assert_eq!(len, Ok(3));
+            assert_eq!(&values[..3], &[2.0, 3.0, 5.0]);
+
+            let mut empty_keys: Vec<i32> = Vec::new();
+            let mut empty_values: Vec<i64> = Vec::new();
+            let len = reduce_by_key(
+                &mut empty_keys,
+                &mut empty_values,
+                |a, b| a == b,
+                |a, b| a + b,
+            );
+            assert_eq!(len, Ok(0));
+
+            let mut values = vec![1, 2];
+            let result = reduce_by_key(&mut keys, &mut values, |a, b| a == b, |a, b| a + b);
+            assert_eq!(result, Err("keys and values differ in length"));
+
+            // values that are not `Copy`, reduced by a closure capturing a
+            // separator
+            let separator = String::from("+");
+            let mut keys = vec![1, 1, 2];
+            let mut values = vec![String::from("a"), String::from("b"), String::from("c")];
+            let join = |a: &String, b: &String| format!("{a}{separator}{b}");
+            assert_eq!(
+                reduce_by_key(&mut keys, &mut values, |a, b| a == b, join),
+                Ok(2)
+            );
+            assert_eq!(&values[..2], &["a+b", "c"]);
+
+            let eq = |a: usize, b: usize| a == b;
+            let result = ffi::hpx_reduce_by_key_indices(3, &mut [0; 2], &IndexCompare::new(&eq));
+            assert!(result.is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_sort_by_key() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_sort_by_key"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut keys = vec![3, 1, 2, 1, 3];
+            let mut values = vec!["c", "a", "b", "a", "c"];
+            assert!(unsafe { sort_by_key(&mut keys, &mut values, |a, b| a < b) }.is_ok());
+            assert_eq!(keys, vec![1, 1, 2, 3, 3]);
+            assert_eq!(values, vec!["a", "a", "b", "c", "c"]);
+
+            // sorting in descending order, values are not `Copy`
+            let mut keys: Vec<u64> = vec![10, 30, 20];
+            let mut values = vec![
+                String::from("ten"),
+                String::from("thirty"),
+                String::from("twenty"),
+            ];
+            assert!(unsafe { sort_by_key(&mut keys, &mut values, |a, b| a > b) }.is_ok());
+            assert_eq!(keys, vec![30, 20, 10]);
+            assert_eq!(values, vec!["thirty", "twenty", "ten"]);
+
+            let result = unsafe { sort_by_key(&mut keys, &mut values[..2], |a, b| a < b) };
+            assert_eq!(result, Err("keys and values differ in length"));
+
+            let less = |a: usize, b: usize| a < b;
+            let mut short = vec![0; 2];
+            let result =
+                ffi::hpx_sort_by_key_indices(&mut [0, 1, 2], &mut short, &IndexCompare::new(&less));
+            assert!(result.is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_stable_sort() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_stable_sort"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+            unsafe { stable_sort(&mut v, |a, b| a.0 < b.0) };
+            assert_eq!(v, vec![(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+
+            let mut words = vec!["pear", "fig", "apple", "kiwi"];
+            unsafe { stable_sort(&mut words, |a, b| a.len() < b.len()) };
+            assert_eq!(words, vec!["fig", "pear", "kiwi", "apple"]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_nth_element() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_nth_element"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![5.0, 2.5, 8.0, 1.0, 9.5, 3.0, 7.0];
+            let mid = v.len() / 2;
+            assert!(unsafe { nth_element(&mut v, mid, |a, b| a < b) }.is_ok());
+            assert_eq!(v[mid], 5.0); // median
+            assert!(v[..mid].iter().all(|&x| x <= v[mid]));
+            assert!(v[mid + 1..].iter().all(|&x| x >= v[mid]));
+
+            let result = unsafe { nth_element(&mut v, 7, |a, b| a < b) };
+            assert_eq!(result, Err("nth larger than source slice length"));
+
+            let less = |a: usize, b: usize| v[a] < v[b];
+            let mut indices: Vec<usize> = (0..v.len()).collect();
+            let result = ffi::hpx_nth_element_indices(&mut indices, 8, &IndexCompare::new(&less));
+            assert!(result.is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_partial_sort_copy() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_partial_sort_copy"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let src = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            let mut dest = vec![0; 3];
+            assert_eq!(
+                unsafe { partial_sort_copy(&src, &mut dest, |a, b| a < b) },
+                3
+            );
+            assert_eq!(dest, vec![1, 2, 3]);
+
+            // destination longer than the source
+            let src = vec![String::from("b"), String::from("a")];
+            let mut dest = vec![String::new(); 4];
+            assert_eq!(
+                unsafe { partial_sort_copy(&src, &mut dest, |a, b| a < b) },
+                2
+            );
+            assert_eq!(dest, vec!["a", "b", "", ""]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_is_sorted() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_is_sorted"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1, 2, 2, 4, 3, 5];
+            assert!(!is_sorted(&v, |a, b| a < b));
+            assert!(is_sorted(&v[..4], |a, b| a < b));
+            assert_eq!(is_sorted_until(&v, |a, b| a < b), 4);
+
+            let empty: Vec<f64> = Vec::new();
+            assert!(is_sorted(&empty, |a, b| a < b));
+            assert_eq!(is_sorted_until(&empty, |a, b| a < b), 0);
+
+            let desc = vec!["c", "b", "a"];
+            assert!(is_sorted(&desc, |a, b| a > b));
+            assert_eq!(is_sorted_until(&desc, |a, b| a > b), 3);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_par_sort() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_par_sort"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            unsafe { v.par_sort_unstable_by(|a, b| b.cmp(a)) };
+            assert_eq!(v, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+
+            let mut people = vec![("carol", 35), ("alice", 30), ("bob", 30), ("dave", 25)];
+            unsafe { people.par_sort_by_key(|p| p.1) };
+            assert_eq!(
+                people,
+                vec![("dave", 25), ("alice", 30), ("bob", 30), ("carol", 35)]
+            );
+
+            let mut floats = vec![2.5, -1.0, 0.0, 10.0];
+            unsafe { floats.par_sort_by(|a: &f64, b| a.total_cmp(b)) };
+            assert_eq!(floats, vec![-1.0, 0.0, 2.5, 10.0]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_merge_by() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_merge_by"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v1 = vec![9, 7, 5, 3];
+            let v2 = vec![8, 7, 2];
+            let mut dest = vec![0; 7];
+            assert!(unsafe { merge_by(&v1, &v2, &mut dest, |a, b| a > b) }.is_ok());
+            assert_eq!(dest, vec![9, 8, 7, 7, 5, 3, 2]);
+
+            // equal keys are taken from the first slice first
+            let v1 = vec![(1, "first"), (2, "first")];
+            let v2 = vec![(1, "second"), (3, "second")];
+            let mut dest = vec![(0, ""); 4];
+            assert!(unsafe { merge_by(&v1, &v2, &mut dest, |a, b| a.0 < b.0) }.is_ok());
+            assert_eq!(
+                dest,
+                vec![(1, "first"), (1, "second"), (2, "first"), (3, "second")]
+            );
+
+            let mut short = vec![(0, ""); 3];
+            let result = unsafe { merge_by(&v1, &v2, &mut short, |a, b| a.0 < b.0) };
+            assert_eq!(
+                result,
+                Err("destination length differs from the combined source length")
+            );
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_inplace_merge() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_inplace_merge"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![1, 4, 6, 8, 2, 3, 5, 7, 9];
+            assert!(unsafe { inplace_merge(&mut v, 4, |a, b| a < b) }.is_ok());
+            assert_eq!(v, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+            let mut words = vec![String::from("b"), String::from("d"), String::from("a")];
+            assert!(unsafe { inplace_merge(&mut words, 2, |a, b| a < b) }.is_ok());
+            assert_eq!(words, vec!["a", "b", "d"]);
+
+            let result = unsafe { inplace_merge(&mut v, 10, |a, b| a < b) };
+            assert_eq!(result, Err("middle larger than source slice length"));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_find_if() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_find_if"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1.0, 2.0, f64::NAN, 4.0, f64::NAN];
+            assert_eq!(find_if(&v, |x| x.is_nan()), Some(2));
+            assert_eq!(find_if(&v, |&x| x > 10.0), None);
+            assert_eq!(find_if_not(&v, |&x| x < 2.0), Some(1));
+            assert_eq!(find_if_not(&v[..2], |x| !x.is_nan()), None);
+
+            let empty: Vec<String> = Vec::new();
+            assert_eq!(find_if(&empty, |s| s.is_empty()), None);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_search() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_search"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![1, 2, 3, 1, 2, 3, 4];
+            assert_eq!(search(&v, &[1, 2, 3], |a, b| a == b), Some(0));
+            assert_eq!(search(&v, &[3, 4], |a, b| a == b), Some(5));
+            assert_eq!(search(&v, &[4, 5], |a, b| a == b), None);
+            assert_eq!(search(&v, &[] as &[i32], |a, b| a == b), Some(0));
+
+            assert_eq!(find_end(&v, &[1, 2, 3], |a, b| a == b), Some(3));
+            assert_eq!(find_end(&v, &[5], |a, b| a == b), None);
+
+            // comparing elements of different types
+            let text = vec!["a", "bb", "ccc", "dddd"];
+            assert_eq!(search(&text, &[2, 3], |s, &n| s.len() == n), Some(1));
+
+            let w = vec![0, 7, 7, 1, 7, 7, 7, 2];
+            assert_eq!(search_n(&w, 3, &7, |a, b| a == b), Some(4));
+            assert_eq!(search_n(&w, 2, &7, |a, b| a == b), Some(1));
+            assert_eq!(search_n(&w, 4, &7, |a, b| a == b), None);
+            assert_eq!(search_n(&w, 0, &7, |a, b| a == b), Some(0));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_find_first_of() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_find_first_of"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec!['h', 'e', 'l', 'l', 'o'];
+            let vowels = ['a', 'e', 'i', 'o', 'u'];
+            assert_eq!(find_first_of(&v, &vowels, |a, b| a == b), Some(1));
+            assert_eq!(find_first_of(&v[2..4], &vowels, |a, b| a == b), None);
+
+            assert_eq!(adjacent_find(&v, |a, b| a == b), Some(2));
+            assert_eq!(adjacent_find(&vowels, |a, b| a == b), None);
+            // first position where the sequence stops increasing
+            assert_eq!(adjacent_find(&[1, 3, 5, 4, 6], |a, b| a >= b), Some(2));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_mismatch() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_mismatch"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v1 = vec![1, 2, 3, 4, 5];
+            let v2 = vec![1, 2, 0, 4, 5];
+            assert_eq!(mismatch(&v1, &v2, |a, b| a == b), Some((2, 2)));
+            assert_eq!(mismatch(&v1, &v1, |a, b| a == b), None);
+            assert_eq!(mismatch(&v1, &v1[..3], |a, b| a == b), Some((3, 3)));
+
+            assert!(starts_with(&v1, &[1, 2], |a, b| a == b));
+            assert!(!starts_with(&v1, &v2, |a, b| a == b));
+            assert!(starts_with(&v1, &[] as &[i32], |a, b| a == b));
+            assert!(!starts_with(&v1[..1], &v1, |a, b| a == b));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_all_any_none_of() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_all_any_none_of"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let samples = vec![0.5, 1.5, f64::NAN, 3.0];
+            assert!(any_of(&samples, |x| x.is_nan()));
+            assert!(!all_of(&samples, |x| x.is_finite()));
+            assert!(none_of(&samples[..2], |x| x.is_nan()));
+            assert!(!none_of(&samples, |&x| x > 2.0));
+
+            let empty: Vec<i32> = Vec::new();
+            assert!(all_of(&empty, |&x| x > 0));
+            assert!(!any_of(&empty, |&x| x > 0));
+            assert!(none_of(&empty, |&x| x > 0));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_min_max_element() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_min_max_element"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v = vec![3, 1, 4, 1, 5, 9, 2, 6, 9];
+            assert_eq!(min_element(&v), Some(1));
+            assert_eq!(max_element(&v), Some(5));
+            assert_eq!(minmax_element(&v), Some((1, 8)));
+
+            // peak of a signal, by absolute value
+            let signal = vec![0.5, -2.0, 1.5, -0.25];
+            assert_eq!(
+                max_element_by(&signal, |a: &f64, b| a.abs() < b.abs()),
+                Some(1)
+            );
+            assert_eq!(
+                min_element_by(&signal, |a: &f64, b| a.abs() < b.abs()),
+                Some(3)
+            );
+
+            let words = vec!["pear", "fig", "banana"];
+            assert_eq!(
+                minmax_element_by(&words, |a, b| a.len() < b.len()),
+                Some((1, 2))
+            );
+
+            let empty: Vec<f64> = Vec::new();
+            assert_eq!(min_element(&empty), None);
+            assert_eq!(max_element(&empty), None);
+            assert_eq!(minmax_element(&empty), None);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_replace() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_replace"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![1, 2, 3, 2, 5];
+            replace(&mut v, 2, 0);
+            assert_eq!(v, vec![1, 0, 3, 0, 5]);
+
+            let mut samples = vec![1.5, f64::NAN, 2.5, f64::NAN];
+            replace_if(&mut samples, |x: &f64| x.is_nan(), 0.0);
+            assert_eq!(samples, vec![1.5, 0.0, 2.5, 0.0]);
+
+            let src: Vec<u64> = vec![7, 8, 7];
+            assert_eq!(replace_copy(&src, 7, 1), vec![1, 8, 1]);
+            assert_eq!(src, vec![7, 8, 7]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_remove() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_remove"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![1, 0, 2, 0, 3];
+            let len = remove(&mut v, 0);
+            assert_eq!(len, 3);
+            assert_eq!(&v[..len], &[1, 2, 3]);
+
+            let mut v2 = vec![1, 2, 3, 4, 5, 6];
+            let len = remove_if(&mut v2, |x| x % 2 == 0);
+            assert_eq!(len, 3);
+            assert_eq!(&v2[..len], &[1, 3, 5]);
+
+            let mut v3: Vec<i64> = vec![-1, 4, -1, 5];
+            assert_eq!(erase(&mut v3, -1), 2);
+            assert_eq!(v3, vec![4, 5]);
+
+            let mut samples = vec![1.0, f64::NAN, 2.0];
+            assert_eq!(erase_if(&mut samples, |x: &f64| x.is_nan()), 1);
+            assert_eq!(samples, vec![1.0, 2.0]);
+
+            let min_len = 3;
+            let mut words = vec!["to", "be", "or", "not", "to", "be", "honest"];
+            assert_eq!(erase_if(&mut words, |w| w.len() < min_len), 5);
+            assert_eq!(words, vec!["not", "honest"]);
+
+            ffi::finalize()
         };
 
         unsafe {
-            let result = ffi::init(dummy_main, argc, argv.as_mut_ptr());
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
             assert_eq!(result, 0);
         }
     }
 
     #[test]
     #[serial]
-    fn test_hpx_copy() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_copy"]);
+    fn test_hpx_unique() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_unique"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let src = vec![1, 2, 3, 4, 5];
-            let result = copy_vector(&src);
-            assert_eq!(src, result);
+            let mut v = vec![1, 1, 2, 2, 2, 3, 1, 1];
+            let len = unique(&mut v);
+            assert_eq!(&v[..len], &[1, 2, 3, 1]);
+
+            let src: Vec<u64> = vec![5, 5, 6, 7, 7];
+            assert_eq!(unique_copy(&src), vec![5, 6, 7]);
+
+            let empty: Vec<i32> = Vec::new();
+            assert!(unique_copy(&empty).is_empty());
+            assert!(ffi::hpx_unique_copy_u64(&src, &mut []).is_err());
+
             ffi::finalize()
         };
 
@@ -167,13 +3709,28 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_copy_range() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_copy"]);
+    fn test_hpx_reverse_rotate() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_reverse_rotate"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let src = vec![1, 2, 3, 4, 5];
-            let result = copy_vector(&src[0..3]);
-            assert_eq!(&src[0..3], &result);
+            let mut v = vec![1, 2, 3, 4, 5];
+            reverse(&mut v);
+            assert_eq!(v, vec![5, 4, 3, 2, 1]);
+
+            let mut v2: Vec<i64> = vec![1, 2, 3, 4, 5];
+            assert!(rotate(&mut v2, 2).is_ok());
+            assert_eq!(v2, vec![3, 4, 5, 1, 2]);
+            assert_eq!(
+                rotate(&mut v2, 6),
+                Err("middle larger than source slice length")
+            );
+
+            let src = vec![1.0, 2.0, 3.0];
+            assert_eq!(rotate_copy(&src, 1), Ok(vec![2.0, 3.0, 1.0]));
+            assert_eq!(rotate_copy(&src, 3), Ok(vec![1.0, 2.0, 3.0]));
+            assert!(rotate_copy(&src, 4).is_err());
+            assert!(ffi::hpx_rotate_copy_f64(&src, 1, &mut [0.0; 2]).is_err());
+
             ffi::finalize()
         };
 
@@ -185,39 +3742,54 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_copy_n() {
-        let (argc, mut argv) = create_c_args(&["test_copy_n"]);
+    fn test_hpx_shift() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_shift"]);
 
-        let test_func = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let src = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-            match copy_n(&src, 5) {
-                Ok(result) => assert_eq!(result, vec![1, 2, 3, 4, 5]),
-                Err(e) => panic!("Unexpected error: {}", e),
-            }
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![1, 2, 3, 4, 5];
+            shift_left(&mut v, 2);
+            assert_eq!(&v[..3], &[3, 4, 5]);
+
+            let mut v2 = vec![1, 2, 3, 4, 5];
+            shift_right(&mut v2, 2);
+            assert_eq!(&v2[2..], &[1, 2, 3]);
+
+            let mut v3 = vec![1, 2, 3];
+            shift_left(&mut v3, 3);
+            assert_eq!(v3, vec![1, 2, 3]);
 
-            match copy_n(&src, 15) {
-                // expecting error
-                Ok(_) => panic!("Expected error, but got Ok"),
-                Err(e) => assert_eq!(e, "count larger than source slice length"),
-            }
             ffi::finalize()
         };
 
         unsafe {
-            let result = ffi::init(test_func, argc, argv.as_mut_ptr());
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
             assert_eq!(result, 0);
         }
     }
 
     #[test]
     #[serial]
-    fn test_hpx_copy_if() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_copy_if"]);
+    fn test_hpx_partition() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_partition"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let src = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
-            let result = copy_if_divisiblileityby3(&src);
-            assert_eq!(result, vec![0, 3, 6, 9, 12]);
+            let mut v = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
+            let split = partition(&mut v, |&x| x < 5);
+            assert_eq!(split, 4);
+            assert!(v[..split].iter().all(|&x| x < 5));
+            assert!(v[split..].iter().all(|&x| x >= 5));
+
+            let mut v2: Vec<i64> = vec![1, 2, 3, 4, 5, 6];
+            let split = stable_partition(&mut v2, |x| x % 2 == 0);
+            assert_eq!(split, 3);
+            assert_eq!(v2, vec![2, 4, 6, 1, 3, 5]);
+
+            let pivot = v2[2];
+            let mut names = vec!["bob", "carol", "dave", "alice"];
+            let split = stable_partition(&mut names, |name| name.len() as i64 > pivot - 2);
+            assert_eq!(split, 2);
+            assert_eq!(names, vec!["carol", "alice", "bob", "dave"]);
+
             ffi::finalize()
         };
 
@@ -229,13 +3801,27 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_count() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_count"]);
+    fn test_hpx_partition_copy() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_partition_copy"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let vec = vec![1, 2, 3, 2, 4, 2, 5, 2];
-            let result = count(&vec, 2);
-            assert_eq!(result, 4);
+            let src = vec![0.5, -1.0, 2.0, -3.5, 0.0];
+            let (positive, rest) = partition_copy(&src, |&x| x > 0.0);
+            assert_eq!(positive, vec![0.5, 2.0]);
+            assert_eq!(rest, vec![-1.0, -3.5, 0.0]);
+
+            let empty: Vec<u64> = Vec::new();
+            let (yes, no) = partition_copy(&empty, |&x| x > 0);
+            assert!(yes.is_empty() && no.is_empty());
+
+            let positive = |index: usize| src[index] > 0.0;
+            let pred = IndexPredicate::new(&positive);
+            let (mut long, mut short) = (vec![0; src.len()], vec![0; 2]);
+            assert!(
+                ffi::hpx_partition_copy_indices(src.len(), &mut long, &mut short, &pred).is_err()
+            );
+            assert!(ffi::hpx_copy_if_indices(src.len(), &mut short, &pred).is_err());
+
             ffi::finalize()
         };
 
@@ -247,27 +3833,17 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_count_if() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_count_if"]);
+    fn test_hpx_is_partitioned() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_is_partitioned"]);
+
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let vec = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-            let result_even = ffi::hpx_count_if(&vec, |x| x % 2 == 0);
-            assert_eq!(result_even, 5);
-            let result_greater_than_5 = ffi::hpx_count_if(&vec, |x| x > 5);
-            assert_eq!(result_greater_than_5, 5);
-            let is_prime = |n: i32| {
-                if n <= 1 {
-                    return false;
-                }
-                for i in 2..=(n as f64).sqrt() as i32 {
-                    if n % i == 0 {
-                        return false;
-                    }
-                }
-                true
-            };
-            let result_prime = ffi::hpx_count_if(&vec, is_prime);
-            assert_eq!(result_prime, 4);
+            let v = vec!["apple", "avocado", "banana", "cherry"];
+            assert!(is_partitioned(&v, |s| s.starts_with('a')));
+            assert!(!is_partitioned(&v, |s| s.len() > 5));
+
+            let empty: Vec<i32> = Vec::new();
+            assert!(is_partitioned(&empty, |&x| x > 0));
+
             ffi::finalize()
         };
 
@@ -279,31 +3855,51 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_ends_with() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_ends_with"]);
+    fn test_hpx_set_operations() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_set_operations"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let v1 = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-            let v2 = vec![8, 9, 10];
-            let v3 = vec![7, 8, 9];
-            let v4 = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-            let v5: Vec<i32> = vec![];
+            let ids1 = vec![1, 2, 2, 4, 7, 9];
+            let ids2 = vec![2, 3, 4, 4, 9];
+            assert_eq!(set_union(&ids1, &ids2), vec![1, 2, 2, 3, 4, 4, 7, 9]);
+            assert_eq!(set_intersection(&ids1, &ids2), vec![2, 4, 9]);
+            assert_eq!(set_difference(&ids1, &ids2), vec![1, 2, 7]);
+            assert_eq!(set_symmetric_difference(&ids1, &ids2), vec![1, 2, 3, 4, 7]);
 
-            // passing vectors
-            assert!(ffi::hpx_ends_with(&v1, &v2));
-            assert!(!ffi::hpx_ends_with(&v1, &v3));
-            assert!(ffi::hpx_ends_with(&v1, &v4));
-            assert!(ffi::hpx_ends_with(&v1, &v5));
-            assert!(ffi::hpx_ends_with(&v5, &v5));
+            let result = set_union(&ids1, &[]);
+            assert_eq!(result, ids1);
+            assert_eq!(result.capacity(), ids1.len());
+            assert!(set_intersection(&ids1, &[]).is_empty());
 
-            // passing slices
-            assert!(ffi::hpx_ends_with(&v1[5..], &v2));
-            assert!(ffi::hpx_ends_with(&v1[..], &v1[8..]));
-            assert!(!ffi::hpx_ends_with(&v1[..5], &v2));
-            assert!(ffi::hpx_ends_with(&v1[..5], &v1[3..5]));
+            let less = |a: usize, b: usize| a < b;
+            let comp = IndexCompare::new(&less);
+            let mut dest = vec![0; 4];
+            assert!(ffi::hpx_set_union_indices(3, 2, &mut dest, &comp).is_err());
+            assert!(ffi::hpx_set_difference_indices(5, 2, &mut dest, &comp).is_err());
+            assert_eq!(
+                ffi::hpx_set_intersection_indices(4, 6, &mut dest, &comp).ok(),
+                Some(0)
+            );
 
-            assert!(ffi::hpx_ends_with(&v1, &[]));
-            assert!(ffi::hpx_ends_with(&[], &[]));
+            // slices sorted in descending order, elements are not `Copy`
+            let names1 = vec![String::from("carol"), String::from("alice")];
+            let names2 = vec![String::from("bob"), String::from("alice")];
+            assert_eq!(
+                set_union_by(&names1, &names2, |a, b| a > b),
+                vec!["carol", "bob", "alice"]
+            );
+            assert_eq!(
+                set_intersection_by(&names1, &names2, |a, b| a > b),
+                vec!["alice"]
+            );
+            assert_eq!(
+                set_difference_by(&names1, &names2, |a, b| a > b),
+                vec!["carol"]
+            );
+            assert_eq!(
+                set_symmetric_difference_by(&names1, &names2, |a, b| a > b),
+                vec!["carol", "bob"]
+            );
 
             ffi::finalize()
         };
@@ -316,29 +3912,49 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_equal() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_equal"]);
+    fn test_hpx_includes() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_includes"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let v1 = vec![1, 2, 3, 4, 5];
-            let v2 = vec![1, 2, 3, 4, 5];
-            let v3 = vec![1, 2, 3, 4, 6];
-            let v4 = vec![1, 2, 3, 4];
-            let v5 = vec![0, 1, 2, 3, 4, 5, 6];
+            let v = vec![1, 2, 3, 4, 5, 6];
+            assert!(includes(&v, &[2, 4, 6]));
+            assert!(!includes(&v, &[2, 7]));
+            assert!(includes(&v, &[]));
+            assert!(!includes(&[], &[1]));
 
-            // passing vectors
-            assert!(ffi::hpx_equal(&v1, &v2));
-            assert!(!ffi::hpx_equal(&v1, &v3));
-            assert!(!ffi::hpx_equal(&v1, &v4));
+            let desc = vec![9.5, 7.0, 3.25];
+            assert!(includes_by(&desc, &[9.5, 3.25], |a, b| a > b));
 
-            // passing slices
-            assert!(ffi::hpx_equal(&v1[..], &v2[..]));
-            assert!(ffi::hpx_equal(&v1[1..4], &v2[1..4]));
-            assert!(ffi::hpx_equal(&v1[..3], &v4[..3]));
-            assert!(ffi::hpx_equal(&v1[..], &v5[1..6]));
+            ffi::finalize()
+        };
 
-            assert!(ffi::hpx_equal(&v1, &v5[1..6]));
-            assert!(!ffi::hpx_equal(&v1[..4], &v3));
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_generate() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_generate"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut seeds = vec![0u64; 1000];
+            generate(&mut seeds, |i| (i as u64 / 100) * 7919);
+            assert_eq!(seeds[0], 0);
+            assert_eq!(seeds[99], 0);
+            assert_eq!(seeds[100], 7919);
+            assert_eq!(seeds[999], 9 * 7919);
+
+            let mut labels = vec![String::new(); 3];
+            generate(&mut labels, |i| format!("chunk-{}", i));
+            assert_eq!(labels, vec!["chunk-0", "chunk-1", "chunk-2"]);
+
+            let mut v = vec![-1; 5];
+            assert!(generate_n(&mut v, 3, |i| i as i32 * 2).is_ok());
+            assert_eq!(v, vec![0, 2, 4, -1, -1]);
+            assert!(generate_n(&mut v, 6, |i| i as i32).is_err());
 
             ffi::finalize()
         };
@@ -351,21 +3967,28 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_fill() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_fill"]);
+    fn test_hpx_iota_adjacent_difference() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_iota_adjacent_difference"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let mut v = vec![0; 10];
-            ffi::hpx_fill(&mut v, 42);
-            assert!(v.iter().all(|&x| x == 42));
+            let mut ramp = vec![0i64; 5];
+            iota(&mut ramp, 10);
+            assert_eq!(ramp, vec![10, 11, 12, 13, 14]);
 
-            let mut v2 = vec![0; 1_000_000]; // testing on a long vector
-            ffi::hpx_fill(&mut v2, 7);
-            assert!(v2.iter().all(|&x| x == 7));
+            let mut wrapped = [0i32; 3];
+            iota(&mut wrapped, i32::MAX - 1);
+            assert_eq!(wrapped, [i32::MAX - 1, i32::MAX, i32::MIN]);
 
-            let mut v3: Vec<i32> = Vec::new();
-            ffi::hpx_fill(&mut v3, 100);
-            assert!(v3.is_empty());
+            let mut x = vec![0.0; 3];
+            iota(&mut x, 0.5);
+            assert_eq!(x, vec![0.5, 1.5, 2.5]);
+
+            assert_eq!(adjacent_difference(&[1, 4, 9, 16]), vec![1, 3, 5, 7]);
+            assert_eq!(
+                adjacent_difference_by(&[1, 2, 3, 4], |current, previous| current * previous),
+                vec![1, 2, 6, 12]
+            );
+            assert!(adjacent_difference::<i32>(&[]).is_empty());
 
             ffi::finalize()
         };
@@ -378,23 +4001,53 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_find() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_find"]);
+    fn test_hpx_make_heap() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_make_heap"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let v = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+            assert!(!is_heap(&v, |a, b| a < b));
+            unsafe { make_heap(&mut v, |a, b| a < b) };
+            assert!(is_heap(&v, |a, b| a < b));
+            assert_eq!(v[0], 9);
+            assert_eq!(is_heap_until(&v, |a, b| a < b), v.len());
 
-            let result = find(&v, 5); // finding existing value
-            assert_eq!(result, Some(4));
+            // min-heap of non-`Copy` tasks by deadline
+            let mut tasks = vec![
+                (30, String::from("c")),
+                (10, String::from("a")),
+                (20, String::from("b")),
+            ];
+            unsafe { make_heap(&mut tasks, |a, b| a.0 > b.0) };
+            assert_eq!(tasks[0].1, "a");
+            assert!(is_heap(&tasks, |a, b| a.0 > b.0));
 
-            let result = find(&v, 11); // finding non-existing value
-            assert_eq!(result, None);
+            assert_eq!(is_heap_until(&[9, 5, 4, 6, 1], |a, b| a < b), 3);
+            assert!(is_heap::<i32>(&[], |a, b| a < b));
 
-            let result = find(&v, 1);
-            assert_eq!(result, Some(0));
+            ffi::finalize()
+        };
 
-            let result = find(&v, 10);
-            assert_eq!(result, Some(9));
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_hpx_binary_heap_from_vec() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_binary_heap_from_vec"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v: Vec<i64> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+            let heap = unsafe { binary_heap_from_vec(v.clone()) };
+            assert_eq!(heap.len(), v.len());
+            assert_eq!(heap.peek(), Some(&999));
+
+            let mut expected = v;
+            expected.sort();
+            assert_eq!(heap.into_sorted_vec(), expected);
 
             ffi::finalize()
         };
@@ -407,13 +4060,24 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_sort() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_sort"]);
+    fn test_hpx_equal_with_predicate() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_equal_with_predicate"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let mut src = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
-            ffi::hpx_sort(&mut src);
-            assert_eq!(src, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            let expected = vec![0.1 + 0.2, 1.0 / 3.0, 2.0 / 7.0];
+            let computed = vec![0.3, 0.333_333_333_333, 0.285_714_285_714];
+            let close = |a: &f64, b: &f64| (a - b).abs() < 1e-9;
+            assert!(!equal(&expected, &computed, |a, b| a == b));
+            assert!(equal(&expected, &computed, close));
+            assert!(!equal(&expected, &computed[..2], close));
+            assert_eq!(
+                mismatch(&expected, &computed, |a, b| (a - b).abs() < 1e-15),
+                Some((1, 1))
+            );
+
+            let names = vec!["Alice", "BOB"];
+            assert!(equal(&names, &["alice", "bob"], |a, b| a.eq_ignore_ascii_case(b)));
+
             ffi::finalize()
         };
 
@@ -425,27 +4089,30 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_sort_comp() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_sort_comp"]);
+    fn test_hpx_lexicographical_compare() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_lexicographical_compare"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let mut v = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
-            ffi::hpx_sort_comp(&mut v, |a, b| a > b); // sorting in descending order
-            assert_eq!(v, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+            assert_eq!(
+                lexicographical_compare(&[1, 2, 3], &[1, 2, 4]),
+                Ordering::Less
+            );
+            assert_eq!(
+                lexicographical_compare(&[1, 3], &[1, 2, 4]),
+                Ordering::Greater
+            );
+            assert_eq!(lexicographical_compare(&[1, 2], &[1, 2, 0]), Ordering::Less);
+            assert_eq!(
+                lexicographical_compare(&[1.5, 2.5], &[1.5, 2.5]),
+                Ordering::Equal
+            );
+            assert_eq!(lexicographical_compare::<i32>(&[], &[]), Ordering::Equal);
 
-            // sorting even numbers before odd numbers
-            let mut v2 = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
-            ffi::hpx_sort_comp(
-                &mut v2,
-                |a, b| {
-                    if a % 2 == b % 2 {
-                        a < b
-                    } else {
-                        a % 2 == 0
-                    }
-                },
+            let by_len = |a: &&str, b: &&str| a.len() < b.len();
+            assert_eq!(
+                lexicographical_compare_by(&["ccc", "a"], &["bbb", "dd"], by_len),
+                Ordering::Less
             );
-            assert_eq!(v2, vec![2, 4, 6, 8, 1, 3, 5, 7, 9]);
 
             ffi::finalize()
         };
@@ -458,15 +4125,19 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_merge() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_merge"]);
+    fn test_hpx_uninitialized_copy_fill() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_uninitialized_copy_fill"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let v1 = vec![1, 3, 5, 7, 9, 20, 100];
-            let v2 = vec![2, 4, 6, 8, 10, 97];
-            let mut dest = Vec::new();
-            ffi::hpx_merge(&v1, &v2, &mut dest);
-            assert_eq!(dest, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 97, 100]);
+            let src: Vec<f64> = (0..1000).map(|i| i as f64 * 0.5).collect();
+            let dest = uninitialized_copy(&src);
+            assert_eq!(dest, src);
+            assert_eq!(dest.capacity(), src.len());
+
+            assert_eq!(uninitialized_fill(4, 7u64), vec![7, 7, 7, 7]);
+            assert!(uninitialized_fill(0, 1i32).is_empty());
+            assert!(uninitialized_copy::<i64>(&[]).is_empty());
+
             ffi::finalize()
         };
 
@@ -478,24 +4149,35 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_partial_sort() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_partial_sort"]);
+    fn test_hpx_uninitialized_move_destroy() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_uninitialized_move_destroy"]);
 
-        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let mut vec = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
-            let last = 4;
-            println!("Before partial sort: {:?}", vec);
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
 
-            ffi::hpx_partial_sort(&mut vec, last);
-            println!("After partial sort: {:?}", vec);
+        #[derive(Default)]
+        struct Tracked(String);
 
-            // If first -> last elements are sorted
-            assert!(vec[..last].windows(2).all(|w| w[0] <= w[1]));
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, AtomicOrdering::SeqCst);
+            }
+        }
 
-            // If ele of sorted part <=  ele of unsorted part
-            assert!(vec[..last]
-                .iter()
-                .all(|&x| vec[last..].iter().all(|&y| x <= y)));
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let defaults: Vec<Tracked> = uninitialized_default_construct(3);
+            assert_eq!(defaults.len(), 3);
+            assert!(defaults.iter().all(|t| t.0.is_empty()));
+
+            let src: Vec<Tracked> = (0..100).map(|i| Tracked(i.to_string())).collect();
+            let moved = uninitialized_move(src);
+            assert_eq!(DROPS.load(AtomicOrdering::SeqCst), 0);
+            assert_eq!(moved.len(), 100);
+            assert_eq!(moved[42].0, "42");
+
+            destroy(moved);
+            assert_eq!(DROPS.load(AtomicOrdering::SeqCst), 100);
+            destroy(defaults);
+            assert_eq!(DROPS.load(AtomicOrdering::SeqCst), 103);
 
             ffi::finalize()
         };
@@ -508,24 +4190,65 @@ mod tests {
 
     #[test]
     #[serial]
-    fn test_hpx_partial_sort_comp() {
-        let (argc, mut argv) = create_c_args(&["test_hpx_partial_sort_comp"]);
+    fn test_hpx_transform_reduce() {
+        let (argc, mut argv) = create_c_args(&["test_hpx_transform_reduce"]);
 
         let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
-            let mut vec = vec![5, 2, 8, 1, 9, 3, 7, 6, 4];
-            let last = 4;
-            println!("Before partial sort: {:?}", vec);
+            let words = vec!["parallel", "hpx", "rust"];
+            assert_eq!(
+                transform_reduce(&words, 0u64, |a, b| a + b, |w| w.len() as u64),
+                15
+            );
 
-            ffi::hpx_partial_sort_comp(&mut vec, last, |a, b| b < a);
-            println!("After partial sort: {:?}", vec);
+            let v = vec![1.5, -2.0, 3.0];
+            let sum_of_squares = transform_reduce(&v, 0.0, |a, b| a + b, |x| x * x);
+            assert_eq!(sum_of_squares, 15.25);
+            assert_eq!(transform_reduce(&v, f64::MIN, f64::max, |x| x.abs()), 3.0);
+            assert_eq!(
+                transform_reduce::<i32, i32>(&[], 7, |a, b| a + b, |x| *x),
+                7
+            );
 
-            // If first -> last elements are sorted dec
-            assert!(vec[..last].windows(2).all(|w| w[0] >= w[1]));
+            ffi::finalize()
+        };
 
-            // If ele of sorted part >= ele of unsorted part
-            assert!(vec[..last]
-                .iter()
-                .all(|&x| vec[last..].iter().all(|&y| x >= y)));
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_topology() {
+        // hpx_main is a plain fn, so the topology read before the runtime
+        // starts reaches it through a static.
+        static BEFORE: OnceLock<ffi::Topology> = OnceLock::new();
+        let before = BEFORE.get_or_init(topology);
+        assert!(before.sockets >= 1);
+        assert!(before.numa_nodes >= 1);
+        assert!(before.cores >= before.sockets);
+        assert!(before.pus >= before.cores);
+        assert_eq!(before.numa_node_pus.len(), before.numa_nodes);
+        assert_eq!(before.numa_node_pus.iter().sum::<usize>(), before.pus);
+        assert_eq!(before.pu_cores.len(), before.pus);
+        assert!(before.pu_cores.iter().all(|&core| core < before.cores));
+        assert_eq!(before.numa_node_cores.iter().sum::<usize>(), before.cores);
+        assert!(!thread_affinity().is_empty());
+
+        let (argc, mut argv) = create_c_args(&["test_topology"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let during = topology();
+            assert_eq!(&during, BEFORE.get().unwrap());
+            assert!(during.l1_cache_size <= during.l2_cache_size || during.l2_cache_size == 0);
+            // A machine with an L2 cache has an L1 cache as well.
+            if during.l2_cache_size > 0 {
+                assert!(during.l1_cache_size > 0);
+            }
+            let affinity = thread_affinity();
+            assert!(!affinity.is_empty());
+            assert!(affinity.iter().all(|pu| *pu < during.pus));
 
             ffi::finalize()
         };
@@ -534,5 +4257,6 @@ mod tests {
             let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
             assert_eq!(result, 0);
         }
+        assert_eq!(&topology(), before);
     }
 }
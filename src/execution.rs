@@ -0,0 +1,491 @@
+//! Executors and execution policies.
+//!
+//! An executor decides how work is spawned: [`ParallelExecutor`] runs every
+//! task on a new HPX thread, [`SequencedExecutor`] runs them in order on the
+//! caller, and [`ForkJoinExecutor`] keeps its worker threads spinning between
+//! bulk calls, which makes it much faster for tight loops. Work is submitted
+//! directly with [`Executor::async_execute`] and
+//! [`Executor::bulk_async_execute`], or by binding the executor to the
+//! parallel policy:
+//!
+//! ```ignore
+//! let exec = ForkJoinExecutor::new();
+//! execution::for_loop(par.on(&exec), 0..n, |i| step(i));
+//! ```
+
+use crate::resource;
+use crate::thread::{self, Packet, ScheduleHint, ThreadPriority, ThreadStacksize};
+use cxx::UniquePtr;
+use hpx_sys::{ffi, BulkTask, IndexFunction, ThreadTask};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+/// An HPX executor that work can be submitted to.
+pub trait Executor {
+    #[doc(hidden)]
+    fn as_raw(&self) -> &ffi::hpx_executor;
+
+    /// Runs `f` asynchronously and returns a handle to wait for its result.
+    fn async_execute<F, T>(&self, f: F) -> AsyncResult<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let packet: Packet<T> = Arc::new(Mutex::new(None));
+        let task_packet = Arc::clone(&packet);
+        let future = ffi::hpx_executor_async_execute(
+            self.as_raw(),
+            ThreadTask::new(move |_| thread::run_into(&task_packet, f)),
+        );
+        AsyncResult { future, packet }
+    }
+
+    /// Runs `f` on every index below `shape` asynchronously and returns a
+    /// handle to wait for all of them. If calls panic, the handle returns the
+    /// payload of one of the panics.
+    fn bulk_async_execute<F>(&self, shape: usize, f: F) -> AsyncResult<()>
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        let packet: Packet<()> = Arc::new(Mutex::new(Some(Ok(()))));
+        let task_packet = Arc::clone(&packet);
+        let task = BulkTask::new(move |index| {
+            if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(index))) {
+                *task_packet.lock().unwrap() = Some(Err(payload));
+            }
+        });
+        let future = ffi::hpx_executor_bulk_async_execute(self.as_raw(), shape, task);
+        AsyncResult { future, packet }
+    }
+}
+
+/// The result of work submitted to an executor, wrapping `hpx::future<void>`.
+///
+/// Dropping it lets the work finish in the background.
+pub struct AsyncResult<T> {
+    future: UniquePtr<ffi::hpx_future>,
+    packet: Packet<T>,
+}
+
+impl<T> AsyncResult<T> {
+    /// Returns `true` once the work has finished.
+    pub fn is_ready(&self) -> bool {
+        ffi::hpx_future_is_ready(&self.future)
+    }
+
+    /// Waits for the work to finish.
+    pub fn wait(&self) {
+        ffi::hpx_future_wait(&self.future);
+    }
+
+    /// Waits for the work to finish and returns its value, or the payload of
+    /// its panic.
+    pub fn get(self) -> std::thread::Result<T> {
+        self.wait();
+        thread::take_result(&self.packet)
+    }
+}
+
+macro_rules! impl_executor {
+    ($name:ident) => {
+        impl Executor for $name {
+            fn as_raw(&self) -> &ffi::hpx_executor {
+                &self.inner
+            }
+        }
+    };
+    ($name:ident, $new:ident) => {
+        impl_executor!($name);
+
+        impl $name {
+            /// Creates the executor on the default thread pool.
+            pub fn new() -> Self {
+                $name { inner: ffi::$new() }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::new()
+            }
+        }
+    };
+}
+
+/// Runs every task on a new HPX thread, wrapping
+/// `hpx::execution::parallel_executor`.
+///
+/// The threads are created with the executor's priority, stack size and
+/// schedule hint:
+///
+/// ```ignore
+/// let urgent = ParallelExecutor::new().with_priority(ThreadPriority::High);
+/// let result = urgent.async_execute(|| answer_query());
+/// ```
+pub struct ParallelExecutor {
+    inner: UniquePtr<ffi::hpx_executor>,
+    pool: Option<String>,
+    priority: ThreadPriority,
+    stacksize: ThreadStacksize,
+    schedule_hint: ScheduleHint,
+}
+
+impl_executor!(ParallelExecutor);
+
+impl ParallelExecutor {
+    /// Creates the executor on the default thread pool, with the default
+    /// thread parameters.
+    pub fn new() -> Self {
+        Self::with_params(
+            None,
+            ThreadPriority::default(),
+            ThreadStacksize::default(),
+            ScheduleHint::default(),
+        )
+    }
+
+    /// Creates the executor on the thread pool named `pool`, failing if
+    /// there is no such pool. See [`resource`](crate::resource) for creating
+    /// pools.
+    pub fn on_pool(pool: &str) -> Result<Self, &'static str> {
+        if !resource::pool_exists(pool) {
+            return Err("no thread pool with this name");
+        }
+        Ok(Self::with_params(
+            Some(pool.to_string()),
+            ThreadPriority::default(),
+            ThreadStacksize::default(),
+            ScheduleHint::default(),
+        ))
+    }
+
+    fn with_params(
+        pool: Option<String>,
+        priority: ThreadPriority,
+        stacksize: ThreadStacksize,
+        schedule_hint: ScheduleHint,
+    ) -> Self {
+        let (hint_mode, hint) = schedule_hint.to_raw();
+        ParallelExecutor {
+            inner: ffi::hpx_parallel_executor_new(
+                pool.as_deref().unwrap_or(""),
+                priority as i8,
+                stacksize as i8,
+                hint_mode,
+                hint,
+            ),
+            pool,
+            priority,
+            stacksize,
+            schedule_hint,
+        }
+    }
+
+    /// Returns the executor running its threads with `priority`, like
+    /// `hpx::execution::experimental::with_priority`.
+    pub fn with_priority(self, priority: ThreadPriority) -> Self {
+        Self::with_params(self.pool, priority, self.stacksize, self.schedule_hint)
+    }
+
+    /// Returns the executor running its threads on stacks of `stacksize`.
+    pub fn with_stacksize(self, stacksize: ThreadStacksize) -> Self {
+        Self::with_params(self.pool, self.priority, stacksize, self.schedule_hint)
+    }
+
+    /// Returns the executor placing its threads according to
+    /// `schedule_hint`.
+    pub fn with_schedule_hint(self, schedule_hint: ScheduleHint) -> Self {
+        Self::with_params(self.pool, self.priority, self.stacksize, schedule_hint)
+    }
+
+    /// Returns the name of the thread pool of the executor, or `None` if it
+    /// uses the pool of the thread that created it.
+    pub fn pool(&self) -> Option<&str> {
+        self.pool.as_deref()
+    }
+
+    /// Returns the priority of the threads of the executor.
+    pub fn priority(&self) -> ThreadPriority {
+        self.priority
+    }
+
+    /// Returns the stack size of the threads of the executor.
+    pub fn stacksize(&self) -> ThreadStacksize {
+        self.stacksize
+    }
+
+    /// Returns the schedule hint of the threads of the executor.
+    pub fn schedule_hint(&self) -> ScheduleHint {
+        self.schedule_hint
+    }
+}
+
+impl Default for ParallelExecutor {
+    fn default() -> Self {
+        ParallelExecutor::new()
+    }
+}
+
+/// Runs every task to completion on the calling thread before returning,
+/// wrapping `hpx::execution::sequenced_executor`.
+pub struct SequencedExecutor {
+    inner: UniquePtr<ffi::hpx_executor>,
+}
+
+impl_executor!(SequencedExecutor, hpx_sequenced_executor_new);
+
+/// Keeps one HPX thread per worker alive, spinning between bulk calls,
+/// wrapping `hpx::execution::experimental::fork_join_executor`.
+///
+/// The worker threads are busy for the lifetime of the executor, so create
+/// it around a sequence of tight loops and drop it afterwards. It may only be
+/// used by one thread at a time, so it is not `Sync`.
+pub struct ForkJoinExecutor {
+    inner: UniquePtr<ffi::hpx_executor>,
+}
+
+impl_executor!(ForkJoinExecutor);
+
+impl ForkJoinExecutor {
+    /// Creates the executor on the default thread pool.
+    pub fn new() -> Self {
+        ForkJoinExecutor {
+            inner: ffi::hpx_fork_join_executor_new(),
+        }
+    }
+}
+
+impl Default for ForkJoinExecutor {
+    fn default() -> Self {
+        ForkJoinExecutor::new()
+    }
+}
+
+/// Submits work to the scheduler of the default thread pool, wrapping
+/// `hpx::execution::experimental::scheduler_executor`.
+pub struct SchedulerExecutor {
+    inner: UniquePtr<ffi::hpx_executor>,
+}
+
+impl_executor!(SchedulerExecutor, hpx_scheduler_executor_new);
+
+/// A [`ParallelExecutor`] bounding the number of its tasks in flight, wrapping
+/// `hpx::execution::experimental::limiting_executor`.
+///
+/// Submitting work blocks while `upper` tasks are in flight, until their
+/// number drops to `lower`. Dropping the executor waits for all its tasks.
+pub struct LimitingExecutor {
+    inner: UniquePtr<ffi::hpx_executor>,
+}
+
+impl_executor!(LimitingExecutor);
+
+impl LimitingExecutor {
+    /// Creates the executor on the default thread pool, failing unless
+    /// `0 < lower <= upper`.
+    pub fn new(lower: usize, upper: usize) -> Result<Self, &'static str> {
+        if lower == 0 || lower > upper {
+            return Err("limits must satisfy 0 < lower <= upper");
+        }
+        Ok(LimitingExecutor {
+            inner: ffi::hpx_limiting_executor_new(lower, upper),
+        })
+    }
+}
+
+// The executors other than `ForkJoinExecutor` accept work from several
+// threads at once.
+unsafe impl Sync for ParallelExecutor {}
+unsafe impl Sync for SequencedExecutor {}
+unsafe impl Sync for SchedulerExecutor {}
+unsafe impl Sync for LimitingExecutor {}
+
+/// The parallel execution policy, optionally bound to an executor of type
+/// `E`. The policy borrows the executor, so it is only `Send` if the executor
+/// is `Sync`.
+pub struct ParallelPolicy<'e, E = ParallelExecutor> {
+    executor: Option<&'e E>,
+}
+
+impl<E> Clone for ParallelPolicy<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for ParallelPolicy<'_, E> {}
+
+/// The parallel execution policy on the default executor,
+/// `hpx::execution::par`.
+#[allow(non_upper_case_globals)]
+pub const par: ParallelPolicy<'static> = ParallelPolicy { executor: None };
+
+impl<E: Executor> ParallelPolicy<'_, E> {
+    /// Returns the policy running its work on `exec`, like
+    /// `hpx::execution::par.on(exec)`.
+    pub fn on<F: Executor>(self, exec: &F) -> ParallelPolicy<'_, F> {
+        ParallelPolicy {
+            executor: Some(exec),
+        }
+    }
+
+    pub(crate) fn for_each_index(self, len: usize, f: &(dyn Fn(usize) + Sync)) {
+        let f = IndexFunction::new(f);
+        match self.executor {
+            Some(exec) => ffi::hpx_executor_for_each_index(exec.as_raw(), len, &f),
+            None => ffi::hpx_for_each_index(len, &f),
+        }
+    }
+}
+
+/// Calls `f` on every index of `range` under `policy`.
+pub fn for_loop<E: Executor>(
+    policy: ParallelPolicy<E>,
+    range: Range<usize>,
+    f: impl Fn(usize) + Sync,
+) {
+    let start = range.start;
+    policy.for_each_index(range.len(), &|index| f(start + index));
+}
+
+/// Calls `f` on every element of `src` under `policy`.
+pub fn for_each<T: Sync, E: Executor>(policy: ParallelPolicy<E>, src: &[T], f: impl Fn(&T) + Sync) {
+    policy.for_each_index(src.len(), &|index| f(&src[index]));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::execution::{
+        self, par, Executor, ForkJoinExecutor, LimitingExecutor, ParallelExecutor,
+        SchedulerExecutor, SequencedExecutor,
+    };
+    use crate::thread::{ScheduleHint, ThreadPriority, ThreadStacksize};
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn check_executor(exec: &impl Executor) {
+        assert_eq!(exec.async_execute(|| 6 * 7).get().unwrap(), 42);
+        let payload = exec.async_execute(|| panic!("expected panic")).get();
+        assert!(payload.is_err());
+
+        let sum = Arc::new(AtomicUsize::new(0));
+        let task_sum = Arc::clone(&sum);
+        let result = exec.bulk_async_execute(100, move |i| {
+            task_sum.fetch_add(i, Ordering::Relaxed);
+        });
+        result.wait();
+        assert!(result.is_ready());
+        assert!(result.get().is_ok());
+        assert_eq!(sum.load(Ordering::Relaxed), 4950);
+
+        let panicked = exec.bulk_async_execute(10, |i| assert_ne!(i, 3));
+        assert!(panicked.get().is_err());
+
+        let data: Vec<usize> = (0..1000).collect();
+        let sum = AtomicUsize::new(0);
+        execution::for_each(par.on(exec), &data, |x| {
+            sum.fetch_add(*x, Ordering::Relaxed);
+        });
+        assert_eq!(sum.into_inner(), 499500);
+    }
+
+    #[test]
+    #[serial]
+    fn test_execution_executors() {
+        let (argc, mut argv) = create_c_args(&["test_execution_executors"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            check_executor(&ParallelExecutor::new());
+            check_executor(&SequencedExecutor::new());
+            check_executor(&ForkJoinExecutor::new());
+            check_executor(&SchedulerExecutor::new());
+            check_executor(&LimitingExecutor::new(2, 4).unwrap());
+            assert!(LimitingExecutor::new(0, 4).is_err());
+            assert!(LimitingExecutor::new(5, 4).is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_execution_thread_params() {
+        let (argc, mut argv) = create_c_args(&["test_execution_thread_params"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let exec = ParallelExecutor::new();
+            assert_eq!(exec.priority(), ThreadPriority::Default);
+            assert_eq!(exec.stacksize(), ThreadStacksize::Small);
+            assert_eq!(exec.schedule_hint(), ScheduleHint::None);
+
+            let exec = exec
+                .with_priority(ThreadPriority::High)
+                .with_stacksize(ThreadStacksize::Large)
+                .with_schedule_hint(ScheduleHint::Numa(0));
+            assert_eq!(exec.priority(), ThreadPriority::High);
+            assert_eq!(exec.stacksize(), ThreadStacksize::Large);
+            assert_eq!(exec.schedule_hint(), ScheduleHint::Numa(0));
+            check_executor(&exec);
+
+            let exec = exec.with_schedule_hint(ScheduleHint::Thread(0));
+            assert_eq!(exec.priority(), ThreadPriority::High);
+            check_executor(&exec.with_priority(ThreadPriority::Low));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_execution_for_loop() {
+        let (argc, mut argv) = create_c_args(&["test_execution_for_loop"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let sum = AtomicUsize::new(0);
+            execution::for_loop(par, 10..20, |i| {
+                sum.fetch_add(i, Ordering::Relaxed);
+            });
+            assert_eq!(sum.load(Ordering::Relaxed), 145);
+
+            let exec = ForkJoinExecutor::new();
+            let counts: Vec<AtomicUsize> = (0..64).map(|_| AtomicUsize::new(0)).collect();
+            for _ in 0..10 {
+                execution::for_loop(par.on(&exec), 0..counts.len(), |i| {
+                    counts[i].fetch_add(1, Ordering::Relaxed);
+                });
+            }
+            assert!(counts.iter().all(|c| c.load(Ordering::Relaxed) == 10));
+
+            // A sequenced executor runs the submitted work before returning.
+            let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let exec = SequencedExecutor::new();
+            for i in 0..3 {
+                let order = Arc::clone(&order);
+                let result = exec.async_execute(move || order.lock().unwrap().push(i));
+                assert!(result.is_ready());
+            }
+            assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! Rayon style parallel iterators over HPX algorithms.
+//!
+//! A chain such as `v.par_iter().map(f).filter(p)` does no work until it is
+//! consumed. The consumers lower the whole chain onto a single HPX algorithm
+//! over the indices of the source: [`ParallelIterator::for_each`] onto
+//! `hpx::for_each`, [`ParallelIterator::sum`], [`ParallelIterator::reduce`]
+//! and [`ParallelIterator::count`] onto `hpx::transform_reduce`, and
+//! [`ParallelIterator::collect`] onto `hpx::copy_if`.
+//!
+//! All of them must be called from an HPX thread, e.g. inside the function
+//! passed to [`hpx_sys::init`].
+//!
+//! The closures run inside HPX's C++ algorithms, which a Rust panic cannot
+//! unwind through: a panic in a closure passed to any of these adapters or
+//! consumers aborts the process.
+
+use hpx_sys::{
+    ffi, generate_vec, IndexFunction, IndexPredicate, IndexValue, NumericElement, SharedMutPtr,
+    ValueOp,
+};
+use std::marker::PhantomData;
+use std::ops::{Add, Range};
+
+/// A parallel computation over the indices `0..len` of a source, producing at
+/// most one item per index.
+pub trait ParallelIterator: Sized + Sync {
+    type Item: Send;
+
+    /// Number of source indices, before any filtering.
+    #[doc(hidden)]
+    fn base_len(&self) -> usize;
+
+    /// The item for source index `index`, or `None` if it was filtered out.
+    ///
+    /// # Safety
+    ///
+    /// Every index below [`base_len`](ParallelIterator::base_len) may be
+    /// passed at most once over the lifetime of the iterator, as mutable
+    /// sources hand out a unique reference per index.
+    #[doc(hidden)]
+    unsafe fn get(&self, index: usize) -> Option<Self::Item>;
+
+    /// Applies `f` to each item.
+    fn map<R: Send, F: Fn(Self::Item) -> R + Sync>(self, f: F) -> Map<Self, F> {
+        Map { base: self, f }
+    }
+
+    /// Keeps the items for which `pred` holds.
+    fn filter<P: Fn(&Self::Item) -> bool + Sync>(self, pred: P) -> Filter<Self, P> {
+        Filter { base: self, pred }
+    }
+
+    /// Calls `f` on each item.
+    fn for_each(self, f: impl Fn(Self::Item) + Sync) {
+        let call = |index: usize| {
+            // SAFETY: `hpx::for_each` visits every index once.
+            if let Some(item) = unsafe { self.get(index) } {
+                f(item)
+            }
+        };
+        ffi::hpx_for_each_index(self.base_len(), &IndexFunction::new(&call));
+    }
+
+    /// Combines the items with `op`, which must be associative, starting from
+    /// `identity`. Unlike rayon the items must be numbers, as HPX reduces them
+    /// in C++.
+    fn reduce(
+        self,
+        identity: Self::Item,
+        op: impl Fn(Self::Item, Self::Item) -> Self::Item + Sync,
+    ) -> Self::Item
+    where
+        Self::Item: NumericElement,
+    {
+        let conv = |index: usize| {
+            // SAFETY: `hpx::transform_reduce` transforms every index once.
+            unsafe { self.get(index) }.unwrap_or(identity)
+        };
+        Self::Item::transform_reduce(
+            self.base_len(),
+            identity,
+            &ValueOp::new(&op),
+            &IndexValue::new(&conv),
+        )
+    }
+
+    /// Adds up the items, see [`reduce`](ParallelIterator::reduce).
+    fn sum(self) -> Self::Item
+    where
+        Self::Item: NumericElement + Add<Output = Self::Item>,
+    {
+        self.reduce(Self::Item::default(), |a, b| a + b)
+    }
+
+    /// Counts the items.
+    fn count(self) -> usize {
+        let conv = |index: usize| {
+            // SAFETY: as in `reduce`.
+            unsafe { self.get(index) }.is_some() as u64
+        };
+        let add = |a, b| a + b;
+        u64::transform_reduce(
+            self.base_len(),
+            0,
+            &ValueOp::new(&add),
+            &IndexValue::new(&conv),
+        ) as usize
+    }
+
+    /// Gathers the items, in source order, into a collection such as `Vec`.
+    fn collect<C: FromParallelIterator<Self::Item>>(self) -> C {
+        C::from_par_iter(self)
+    }
+}
+
+/// Conversion into a [`ParallelIterator`], the parallel counterpart of
+/// `IntoIterator`.
+pub trait IntoParallelIterator {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item: Send;
+
+    fn into_par_iter(self) -> Self::Iter;
+}
+
+/// Provides `par_iter()` for every type whose shared reference is an
+/// [`IntoParallelIterator`], such as `Vec<T>` and `[T]`.
+pub trait IntoParallelRefIterator<'data> {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item: Send + 'data;
+
+    fn par_iter(&'data self) -> Self::Iter;
+}
+
+impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+where
+    &'data I: IntoParallelIterator,
+{
+    type Iter = <&'data I as IntoParallelIterator>::Iter;
+    type Item = <&'data I as IntoParallelIterator>::Item;
+
+    fn par_iter(&'data self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+/// Provides `par_iter_mut()` for every type whose mutable reference is an
+/// [`IntoParallelIterator`].
+pub trait IntoParallelRefMutIterator<'data> {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item: Send + 'data;
+
+    fn par_iter_mut(&'data mut self) -> Self::Iter;
+}
+
+impl<'data, I: 'data + ?Sized> IntoParallelRefMutIterator<'data> for I
+where
+    &'data mut I: IntoParallelIterator,
+{
+    type Iter = <&'data mut I as IntoParallelIterator>::Iter;
+    type Item = <&'data mut I as IntoParallelIterator>::Item;
+
+    fn par_iter_mut(&'data mut self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+/// Collections that can be built from a [`ParallelIterator`].
+pub trait FromParallelIterator<T: Send> {
+    fn from_par_iter<I: ParallelIterator<Item = T>>(par_iter: I) -> Self;
+}
+
+impl<T: Send> FromParallelIterator<T> for Vec<T> {
+    fn from_par_iter<I: ParallelIterator<Item = T>>(par_iter: I) -> Self {
+        // Evaluate the chain once per index, then let `hpx::copy_if` find the
+        // items that survived filtering and move them into place.
+        let mut slots: Vec<Option<T>> = generate_vec(par_iter.base_len(), |index| {
+            // SAFETY: `generate_vec` visits every index once.
+            unsafe { par_iter.get(index) }
+        });
+        let mut kept = vec![0; slots.len()];
+        let slots_len = slots.len();
+        let slots = SharedMutPtr::new(slots.as_mut_ptr());
+        // SAFETY: only the discriminant is read, and nothing writes the slots
+        // until `copy_if` returns.
+        let is_some = |index: usize| unsafe { (*slots.get().add(index)).is_some() };
+        let len = ffi::hpx_copy_if_indices(slots_len, &mut kept, &IndexPredicate::new(&is_some))
+            .expect("destination is as long as the source");
+
+        generate_vec(len, |k| {
+            // SAFETY: `kept` holds distinct indices, so each slot is taken by
+            // exactly one thread.
+            unsafe { (*slots.get().add(kept[k])).take() }.unwrap()
+        })
+    }
+}
+
+/// Parallel iterator over shared references to the elements of a slice.
+pub struct Iter<'data, T> {
+    slice: &'data [T],
+}
+
+impl<'data, T: Sync + 'data> ParallelIterator for Iter<'data, T> {
+    type Item = &'data T;
+
+    fn base_len(&self) -> usize {
+        self.slice.len()
+    }
+
+    unsafe fn get(&self, index: usize) -> Option<&'data T> {
+        Some(&self.slice[index])
+    }
+}
+
+impl<'data, T: Sync + 'data> IntoParallelIterator for &'data [T] {
+    type Iter = Iter<'data, T>;
+    type Item = &'data T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        Iter { slice: self }
+    }
+}
+
+impl<'data, T: Sync + 'data> IntoParallelIterator for &'data Vec<T> {
+    type Iter = Iter<'data, T>;
+    type Item = &'data T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        Iter { slice: self }
+    }
+}
+
+/// Parallel iterator over mutable references to the elements of a slice.
+pub struct IterMut<'data, T> {
+    ptr: SharedMutPtr<T>,
+    len: usize,
+    marker: PhantomData<&'data mut [T]>,
+}
+
+// The marker makes `IterMut` only as `Sync` as `&mut [T]`, but each `&mut T`
+// it hands out goes to a single thread, as with `Send` for `&mut T`.
+unsafe impl<T: Send> Sync for IterMut<'_, T> {}
+
+impl<'data, T: Send + 'data> ParallelIterator for IterMut<'data, T> {
+    type Item = &'data mut T;
+
+    fn base_len(&self) -> usize {
+        self.len
+    }
+
+    unsafe fn get(&self, index: usize) -> Option<&'data mut T> {
+        assert!(index < self.len);
+        // SAFETY: the caller passes each index at most once, so the mutable
+        // references handed out never alias.
+        Some(&mut *self.ptr.get().add(index))
+    }
+}
+
+impl<'data, T: Send + 'data> IntoParallelIterator for &'data mut [T] {
+    type Iter = IterMut<'data, T>;
+    type Item = &'data mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        IterMut {
+            ptr: SharedMutPtr::new(self.as_mut_ptr()),
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'data, T: Send + 'data> IntoParallelIterator for &'data mut Vec<T> {
+    type Iter = IterMut<'data, T>;
+    type Item = &'data mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_mut_slice().into_par_iter()
+    }
+}
+
+/// Parallel iterator over a range of indices.
+pub struct RangeIter {
+    range: Range<usize>,
+}
+
+impl ParallelIterator for RangeIter {
+    type Item = usize;
+
+    fn base_len(&self) -> usize {
+        self.range.len()
+    }
+
+    unsafe fn get(&self, index: usize) -> Option<usize> {
+        Some(self.range.start + index)
+    }
+}
+
+impl IntoParallelIterator for Range<usize> {
+    type Iter = RangeIter;
+    type Item = usize;
+
+    fn into_par_iter(self) -> Self::Iter {
+        RangeIter { range: self }
+    }
+}
+
+/// Parallel iterator returned by [`ParallelIterator::map`].
+pub struct Map<I, F> {
+    base: I,
+    f: F,
+}
+
+impl<I, F, R> ParallelIterator for Map<I, F>
+where
+    I: ParallelIterator,
+    F: Fn(I::Item) -> R + Sync,
+    R: Send,
+{
+    type Item = R;
+
+    fn base_len(&self) -> usize {
+        self.base.base_len()
+    }
+
+    unsafe fn get(&self, index: usize) -> Option<R> {
+        self.base.get(index).map(&self.f)
+    }
+}
+
+/// Parallel iterator returned by [`ParallelIterator::filter`].
+pub struct Filter<I, P> {
+    base: I,
+    pred: P,
+}
+
+impl<I, P> ParallelIterator for Filter<I, P>
+where
+    I: ParallelIterator,
+    P: Fn(&I::Item) -> bool + Sync,
+{
+    type Item = I::Item;
+
+    fn base_len(&self) -> usize {
+        self.base.base_len()
+    }
+
+    unsafe fn get(&self, index: usize) -> Option<I::Item> {
+        self.base.get(index).filter(&self.pred)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+
+    #[test]
+    #[serial]
+    fn test_par_iter_map_filter_sum() {
+        let (argc, mut argv) = create_c_args(&["test_par_iter_map_filter_sum"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let v: Vec<i64> = (1..=100).collect();
+            assert_eq!(v.par_iter().map(|x| x * 2).sum(), 10100);
+            assert_eq!(v.par_iter().map(|x| *x).filter(|x| x % 2 == 0).sum(), 2550);
+            assert_eq!(v.par_iter().filter(|x| **x > 90).count(), 10);
+            assert_eq!(
+                (0..10)
+                    .into_par_iter()
+                    .map(|i| i as f64)
+                    .reduce(0.0, f64::max),
+                9.0
+            );
+
+            let empty: Vec<u64> = Vec::new();
+            assert_eq!(empty.par_iter().map(|x| *x).sum(), 0);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_par_iter_collect() {
+        let (argc, mut argv) = create_c_args(&["test_par_iter_collect"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let words = vec!["alpha", "beta", "gamma", "delta"];
+            let long: Vec<String> = words
+                .par_iter()
+                .filter(|w| w.len() > 4)
+                .map(|w| w.to_uppercase())
+                .collect();
+            assert_eq!(long, vec!["ALPHA", "GAMMA", "DELTA"]);
+
+            let squares: Vec<usize> = (0..5).into_par_iter().map(|i| i * i).collect();
+            assert_eq!(squares, vec![0, 1, 4, 9, 16]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_par_iter_mut() {
+        let (argc, mut argv) = create_c_args(&["test_par_iter_mut"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut v = vec![1, 2, 3, 4];
+            v.par_iter_mut().for_each(|x| *x *= 10);
+            assert_eq!(v, vec![10, 20, 30, 40]);
+
+            v.par_iter_mut().filter(|x| **x > 15).for_each(|x| *x = 0);
+            assert_eq!(v, vec![10, 0, 0, 0]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
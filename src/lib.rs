@@ -12,3 +12,20 @@
 //! export DYLD_LIBRARY_PATH=$HPX_LOCATION/lib:$DYLD_LIBRARY_PATH
 //! ```
 //! [1]: https://hpx.stellar-group.org/
+
+pub mod execution;
+pub mod iter;
+pub mod performance_counters;
+pub mod ranges;
+pub mod resource;
+pub mod stop_token;
+pub mod this_thread;
+pub mod thread;
+
+/// The traits needed to use the parallel iterators in [`iter`].
+pub mod prelude {
+    pub use crate::iter::{
+        FromParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+        IntoParallelRefMutIterator, ParallelIterator,
+    };
+}
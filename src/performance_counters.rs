@@ -0,0 +1,360 @@
+//! Reading HPX performance counters from within the application.
+//!
+//! Counters are named by their type and an instance, like
+//! `/threads{locality#0/total}/count/cumulative`. [`discover_counter_types`]
+//! lists the types the runtime knows, and a [`PerformanceCounter`] reads one
+//! instance:
+//!
+//! ```ignore
+//! fn uptime() -> Result<f64, String> {
+//!     let uptime = PerformanceCounter::new("/runtime{locality#0/total}/uptime")?;
+//!     uptime.get_value()
+//! }
+//! ```
+//!
+//! Failures carry the runtime's message. Counters live in the runtime, so these must run on an HPX thread, except
+//! [`install_counter_type`] which adds the application's own counters and may
+//! also be called before the runtime starts.
+
+use cxx::UniquePtr;
+use hpx_sys::{ffi, CounterCallback};
+
+pub use hpx_sys::ffi::CounterInfo;
+
+/// Returns the counter types known to the runtime. Their names have no
+/// instance part.
+pub fn discover_counter_types() -> Vec<CounterInfo> {
+    let mut infos = Vec::new();
+    ffi::hpx_discover_counter_types(&mut infos);
+    infos
+}
+
+/// Adds a raw counter type named `name`, whose value is returned by `f`.
+///
+/// `f` receives `true` when the counter is read with a reset, and should then
+/// restart counting from its initial value. It may be called from any thread
+/// and must not panic. The name has the form `/object/counter`, like
+/// `/app/queue-depth`, and instances are named
+/// `/app{locality#0/total}/queue-depth`.
+///
+/// Called before [`ffi::init`], the type is installed while the runtime
+/// starts, so that `--hpx:print-counter` and other tooling find it. Called on
+/// an HPX thread, it is installed immediately and fails if the name is
+/// already taken.
+pub fn install_counter_type<F>(
+    name: &str,
+    help_text: &str,
+    unit_of_measure: &str,
+    f: F,
+) -> Result<(), String>
+where
+    F: Fn(bool) -> i64 + Send + Sync + 'static,
+{
+    let object = name.strip_prefix('/').and_then(|rest| rest.split_once('/'));
+    if !matches!(object, Some((object, counter)) if !object.is_empty() && !counter.is_empty()) {
+        return Err("counter type names have the form /object/counter".to_string());
+    }
+    ffi::hpx_install_counter_type(name, help_text, unit_of_measure, CounterCallback::new(f))
+        .map_err(|e| e.what().to_string())
+}
+
+/// Whether a [`CounterValue`] holds a usable value,
+/// `hpx::performance_counters::counter_status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CounterStatus {
+    /// The value is valid.
+    ValidData,
+    /// The value is valid and changed since the last read.
+    NewData,
+    InvalidData,
+    AlreadyDefined,
+    CounterUnknown,
+    CounterTypeUnknown,
+    GenericError,
+}
+
+impl CounterStatus {
+    fn from_raw(status: i32) -> Self {
+        match status {
+            0 => CounterStatus::ValidData,
+            1 => CounterStatus::NewData,
+            2 => CounterStatus::InvalidData,
+            3 => CounterStatus::AlreadyDefined,
+            4 => CounterStatus::CounterUnknown,
+            5 => CounterStatus::CounterTypeUnknown,
+            _ => CounterStatus::GenericError,
+        }
+    }
+
+    /// Returns `true` for `ValidData` and `NewData`.
+    pub fn is_valid(self) -> bool {
+        matches!(self, CounterStatus::ValidData | CounterStatus::NewData)
+    }
+}
+
+/// A value read from a performance counter.
+///
+/// `value` is the raw reading. The value in the counter's unit of measure is
+/// `value / scaling` if `scale_inverse` is set and `value * scaling`
+/// otherwise, as returned by [`CounterValue::get`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CounterValue {
+    /// When the value was taken, in nanoseconds.
+    pub time: u64,
+    /// How often the counter has been read.
+    pub count: u64,
+    pub value: i64,
+    pub scaling: i64,
+    pub scale_inverse: bool,
+    pub status: CounterStatus,
+}
+
+impl CounterValue {
+    /// Returns the scaled value as `T`, failing if the value is not valid.
+    pub fn get<T: CounterValueType>(&self) -> Result<T, String> {
+        if !self.status.is_valid() {
+            return Err("the counter value is not valid".to_string());
+        }
+        if self.scaling == 0 {
+            return Err("the counter value has a scaling of 0".to_string());
+        }
+        Ok(T::from_counter_value(self))
+    }
+}
+
+impl From<ffi::CounterValue> for CounterValue {
+    fn from(value: ffi::CounterValue) -> Self {
+        CounterValue {
+            time: value.time,
+            count: value.count,
+            value: value.value,
+            scaling: value.scaling,
+            scale_inverse: value.scale_inverse,
+            status: CounterStatus::from_raw(value.status),
+        }
+    }
+}
+
+/// The types a [`CounterValue`] can be read as, like the `T` of HPX's
+/// `get_value<T>`.
+pub trait CounterValueType: Sized {
+    /// Returns the scaled value. `value.scaling` is not 0.
+    fn from_counter_value(value: &CounterValue) -> Self;
+}
+
+impl CounterValueType for i64 {
+    fn from_counter_value(value: &CounterValue) -> Self {
+        if value.scale_inverse {
+            value.value / value.scaling
+        } else {
+            value.value.saturating_mul(value.scaling)
+        }
+    }
+}
+
+impl CounterValueType for f64 {
+    fn from_counter_value(value: &CounterValue) -> Self {
+        if value.scale_inverse {
+            value.value as f64 / value.scaling as f64
+        } else {
+            value.value as f64 * value.scaling as f64
+        }
+    }
+}
+
+/// A counter instance, `hpx::performance_counters::performance_counter`.
+pub struct PerformanceCounter(UniquePtr<ffi::hpx_performance_counter>);
+
+impl PerformanceCounter {
+    /// Creates the counter with the full name `name`, failing with the
+    /// runtime's message if its type is unknown or the instance does not
+    /// exist.
+    pub fn new(name: &str) -> Result<Self, String> {
+        ffi::hpx_performance_counter_new(name)
+            .map(PerformanceCounter)
+            .map_err(|e| e.what().to_string())
+    }
+
+    /// Returns the full name, help text and unit of measure of the counter.
+    pub fn info(&self) -> Result<CounterInfo, String> {
+        let mut info = CounterInfo::default();
+        ffi::hpx_performance_counter_get_info(&self.0, &mut info)
+            .map_err(|e| e.what().to_string())?;
+        Ok(info)
+    }
+
+    /// Reads the counter, resetting it afterwards if `reset` is set.
+    pub fn get_counter_value(&self, reset: bool) -> Result<CounterValue, String> {
+        let mut value = ffi::CounterValue::default();
+        ffi::hpx_performance_counter_get_value(&self.0, reset, &mut value)
+            .map_err(|e| e.what().to_string())?;
+        Ok(value.into())
+    }
+
+    /// Reads the counter and returns its scaled value as `T`.
+    pub fn get_value<T: CounterValueType>(&self) -> Result<T, String> {
+        self.get_counter_value(false)?.get()
+    }
+
+    /// Starts the counter. Returns `false` if it was already running.
+    pub fn start(&self) -> Result<bool, String> {
+        ffi::hpx_performance_counter_start(&self.0).map_err(|e| e.what().to_string())
+    }
+
+    /// Stops the counter. Returns `false` if it was not running.
+    pub fn stop(&self) -> Result<bool, String> {
+        ffi::hpx_performance_counter_stop(&self.0).map_err(|e| e.what().to_string())
+    }
+
+    /// Resets the counter to its initial value.
+    pub fn reset(&self) -> Result<(), String> {
+        ffi::hpx_performance_counter_reset(&self.0).map_err(|e| e.what().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::performance_counters::{self, CounterStatus, CounterValue, PerformanceCounter};
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_counter_value_scaling() {
+        let mut value = CounterValue {
+            time: 0,
+            count: 1,
+            value: 2500,
+            scaling: 1000,
+            scale_inverse: true,
+            status: CounterStatus::ValidData,
+        };
+        assert_eq!(value.get::<f64>(), Ok(2.5));
+        assert_eq!(value.get::<i64>(), Ok(2));
+
+        value.scale_inverse = false;
+        assert_eq!(value.get::<i64>(), Ok(2_500_000));
+
+        value.status = CounterStatus::InvalidData;
+        assert!(value.get::<f64>().is_err());
+        value.status = CounterStatus::NewData;
+        value.scaling = 0;
+        assert!(value.get::<f64>().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_performance_counters() {
+        let (argc, mut argv) = create_c_args(&["test_performance_counters"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let types = performance_counters::discover_counter_types();
+            let uptime_type = types
+                .iter()
+                .find(|info| info.name == "/runtime/uptime")
+                .unwrap();
+            assert!(!uptime_type.help_text.is_empty());
+
+            let uptime = PerformanceCounter::new("/runtime{locality#0/total}/uptime").unwrap();
+            assert_eq!(
+                uptime.info().unwrap().name,
+                "/runtime{locality#0/total}/uptime"
+            );
+            assert!(uptime.start().is_ok());
+            let value = uptime.get_counter_value(false).unwrap();
+            assert!(value.status.is_valid());
+            let first: f64 = uptime.get_value().unwrap();
+            let second: f64 = uptime.get_value().unwrap();
+            assert!(first >= 0.0 && second >= first);
+            assert!(uptime.stop().is_ok());
+
+            let threads =
+                PerformanceCounter::new("/threads{locality#0/total}/count/cumulative").unwrap();
+            let before: i64 = threads.get_value().unwrap();
+            assert!(before >= 0);
+            threads.reset().unwrap();
+            let after_reset = threads.get_counter_value(true).unwrap();
+            assert!(after_reset.status.is_valid());
+            assert!(after_reset.count >= 1);
+
+            let error = PerformanceCounter::new("/no/such/counter").err().unwrap();
+            assert!(!error.is_empty());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_custom_performance_counters() {
+        let queue_depth = Arc::new(AtomicI64::new(0));
+        let depth = Arc::clone(&queue_depth);
+        performance_counters::install_counter_type(
+            "/app/queue-depth",
+            "returns the number of queued requests",
+            "",
+            move |_reset| depth.load(Ordering::Relaxed),
+        )
+        .unwrap();
+        assert_eq!(
+            performance_counters::install_counter_type("app", "", "", |_| 0),
+            Err("counter type names have the form /object/counter".to_string())
+        );
+        assert!(performance_counters::install_counter_type("/app/", "", "", |_| 0).is_err());
+
+        let (argc, mut argv) = create_c_args(&["test_custom_performance_counters"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let types = performance_counters::discover_counter_types();
+            let info = types
+                .iter()
+                .find(|info| info.name == "/app/queue-depth")
+                .unwrap();
+            assert_eq!(info.help_text, "returns the number of queued requests");
+
+            let hits = Arc::new(AtomicI64::new(0));
+            let counted = Arc::clone(&hits);
+            performance_counters::install_counter_type(
+                "/app/cache-hits",
+                "returns the number of cache hits",
+                "",
+                move |reset| {
+                    if reset {
+                        counted.swap(0, Ordering::Relaxed)
+                    } else {
+                        counted.load(Ordering::Relaxed)
+                    }
+                },
+            )
+            .unwrap();
+            let error =
+                performance_counters::install_counter_type("/app/cache-hits", "", "", |_| 0)
+                    .err()
+                    .unwrap();
+            assert!(!error.is_empty());
+
+            let depth = PerformanceCounter::new("/app{locality#0/total}/queue-depth").unwrap();
+            assert_eq!(depth.get_value::<i64>(), Ok(7));
+
+            let hits_counter =
+                PerformanceCounter::new("/app{locality#0/total}/cache-hits").unwrap();
+            hits.fetch_add(3, Ordering::Relaxed);
+            assert_eq!(hits_counter.get_value::<i64>(), Ok(3));
+
+            ffi::finalize()
+        };
+
+        queue_depth.store(7, Ordering::Relaxed);
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Range based algorithms in the style of `hpx::ranges`.
+//!
+//! Each algorithm takes anything that views as a slice (`Vec<T>`, arrays,
+//! slices, `Box<[T]>`, ...) through `AsRef<[T]>` or `AsMut<[T]>`, plus a
+//! projection applied to every element before it is compared or tested:
+//!
+//! ```ignore
+//! unsafe { ranges::sort(&mut records, |r| r.timestamp) };
+//! let errors = ranges::count(&records, &Level::Error, |r| r.level);
+//! ```
+//!
+//! Projections are recomputed on every comparison, so they should be cheap,
+//! typically a field access. Pass [`identity`] to use the elements as they
+//! are. Like the rest of the bindings, these must run on an HPX thread.
+
+use hpx_sys::{ffi, IndexCompare, IndexPlace, IndexPredicate, ParallelSort, SharedMutPtr};
+
+/// The projection that leaves elements unchanged, for elements that are
+/// cheap to clone.
+pub fn identity<T: Clone>(value: &T) -> T {
+    value.clone()
+}
+
+/// Sorts `range` by the projected keys. Floats are not `Ord`; sort them with
+/// [`ParallelSort::par_sort_by`] and `f64::total_cmp` instead.
+///
+/// # Safety
+///
+/// The `Ord` implementation of `K` must be a total order, as the derived ones
+/// are. See [`hpx_sys::stable_sort`] for why.
+pub unsafe fn sort<R, T, K>(range: &mut R, proj: impl Fn(&T) -> K + Sync)
+where
+    R: AsMut<[T]> + ?Sized,
+    T: Sync,
+    K: Ord,
+{
+    range
+        .as_mut()
+        .par_sort_unstable_by(|a, b| proj(a).cmp(&proj(b)));
+}
+
+/// Like [`sort`], keeping elements with equal keys in their original order.
+///
+/// # Safety
+///
+/// Same contract as [`sort`].
+pub unsafe fn stable_sort<R, T, K>(range: &mut R, proj: impl Fn(&T) -> K + Sync)
+where
+    R: AsMut<[T]> + ?Sized,
+    T: Sync,
+    K: Ord,
+{
+    range.as_mut().par_sort_by(|a, b| proj(a).cmp(&proj(b)));
+}
+
+/// Returns the number of elements of `range` whose projection equals `value`.
+pub fn count<R, T, K>(range: &R, value: &K, proj: impl Fn(&T) -> K + Sync) -> usize
+where
+    R: AsRef<[T]> + ?Sized,
+    T: Sync,
+    K: PartialEq + Sync,
+{
+    count_if(range, |key| key == value, proj)
+}
+
+/// Returns the number of elements of `range` whose projection satisfies
+/// `pred`.
+pub fn count_if<R, T, K>(
+    range: &R,
+    pred: impl Fn(&K) -> bool + Sync,
+    proj: impl Fn(&T) -> K + Sync,
+) -> usize
+where
+    R: AsRef<[T]> + ?Sized,
+    T: Sync,
+{
+    let src = range.as_ref();
+    let pred_index = |index: usize| pred(&proj(&src[index]));
+    ffi::hpx_count_if_indices(src.len(), &IndexPredicate::new(&pred_index))
+}
+
+/// Copies the elements of `range` whose projection satisfies `pred`, in
+/// order.
+pub fn copy_if<R, T, K>(
+    range: &R,
+    pred: impl Fn(&K) -> bool + Sync,
+    proj: impl Fn(&T) -> K + Sync,
+) -> Vec<T>
+where
+    R: AsRef<[T]> + ?Sized,
+    T: Clone + Sync,
+{
+    let src = range.as_ref();
+    let mut indices = vec![0; src.len()];
+    let pred_index = |index: usize| pred(&proj(&src[index]));
+    let len = ffi::hpx_copy_if_indices(src.len(), &mut indices, &IndexPredicate::new(&pred_index))
+        .expect("destination is as long as the source");
+    indices[..len]
+        .iter()
+        .map(|&index| src[index].clone())
+        .collect()
+}
+
+/// Returns the index of the first element of `range` whose projection equals
+/// `value`.
+pub fn find<R, T, K>(range: &R, value: &K, proj: impl Fn(&T) -> K + Sync) -> Option<usize>
+where
+    R: AsRef<[T]> + ?Sized,
+    T: Sync,
+    K: PartialEq + Sync,
+{
+    hpx_sys::find_if(range.as_ref(), |element| proj(element) == *value)
+}
+
+/// Merges `range1` and `range2`, both sorted by the projected keys, into a new
+/// `Vec`. Elements with equal keys are taken from `range1` first.
+///
+/// # Safety
+///
+/// Same contract as [`sort`].
+pub unsafe fn merge<R1, R2, T, K>(range1: &R1, range2: &R2, proj: impl Fn(&T) -> K + Sync) -> Vec<T>
+where
+    R1: AsRef<[T]> + ?Sized,
+    R2: AsRef<[T]> + ?Sized,
+    T: Clone + Send + Sync,
+    K: Ord,
+{
+    let (src1, src2) = (range1.as_ref(), range2.as_ref());
+    let len = src1.len() + src2.len();
+    let element = |index: usize| {
+        if index < src1.len() {
+            &src1[index]
+        } else {
+            &src2[index - src1.len()]
+        }
+    };
+    let comp = |a: usize, b: usize| proj(element(a)) < proj(element(b));
+    // Each element is cloned once, straight into the spare capacity of `dest`.
+    let mut dest: Vec<T> = Vec::with_capacity(len);
+    let slots = SharedMutPtr::new(dest.as_mut_ptr());
+    let place = |pos: usize, src: usize| {
+        // SAFETY: with a strict weak ordering the merge writes every position
+        // below `len` exactly once; a panic in `clone` or `proj` aborts.
+        unsafe { slots.get().add(pos).write(element(src).clone()) }
+    };
+    ffi::hpx_merge_into(
+        src1.len(),
+        src2.len(),
+        &IndexCompare::new(&comp),
+        &IndexPlace::new(&place),
+    );
+    // SAFETY: all `len` elements were written above.
+    unsafe { dest.set_len(len) };
+    dest
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ranges;
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Record {
+        timestamp: u64,
+        level: u8,
+        message: String,
+    }
+
+    fn record(timestamp: u64, level: u8, message: &str) -> Record {
+        Record {
+            timestamp,
+            level,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_ranges_sort() {
+        let (argc, mut argv) = create_c_args(&["test_ranges_sort"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut records = vec![
+                record(30, 1, "c"),
+                record(10, 2, "a"),
+                record(20, 1, "b"),
+                record(10, 1, "d"),
+            ];
+            unsafe { ranges::stable_sort(&mut records, |r| r.timestamp) };
+            let messages: Vec<&str> = records.iter().map(|r| r.message.as_str()).collect();
+            assert_eq!(messages, vec!["a", "d", "b", "c"]);
+
+            unsafe { ranges::sort(&mut records[..], |r| r.message.clone()) };
+            assert_eq!(records[0].message, "a");
+
+            let mut array = [35, -10, 225];
+            unsafe { ranges::sort(&mut array, ranges::identity) };
+            assert_eq!(array, [-10, 35, 225]);
+
+            let mut boxed: Box<[i32]> = vec![5, -7, 2].into_boxed_slice();
+            unsafe { ranges::sort(&mut boxed, |x: &i32| x.abs()) };
+            assert_eq!(&*boxed, &[2, 5, -7]);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_ranges_count_copy_find() {
+        let (argc, mut argv) = create_c_args(&["test_ranges_count_copy_find"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let records = vec![
+                record(10, 1, "a"),
+                record(20, 3, "b"),
+                record(30, 3, "c"),
+                record(40, 2, "d"),
+            ];
+            assert_eq!(ranges::count(&records, &3, |r| r.level), 2);
+            assert_eq!(ranges::count_if(&records, |t| *t > 15, |r| r.timestamp), 3);
+            assert_eq!(ranges::count(&[1, 2, 2, 3], &2, ranges::identity), 2);
+
+            let errors = ranges::copy_if(&records, |level| *level == 3, |r| r.level);
+            assert_eq!(errors, vec![records[1].clone(), records[2].clone()]);
+
+            assert_eq!(ranges::find(&records, &30, |r| r.timestamp), Some(2));
+            assert_eq!(ranges::find(&records, &99, |r| r.timestamp), None);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_ranges_merge() {
+        let (argc, mut argv) = create_c_args(&["test_ranges_merge"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let first = vec![record(10, 1, "a"), record(30, 1, "c")];
+            let second = [record(10, 2, "x"), record(20, 2, "b")];
+            let merged = unsafe { ranges::merge(&first, &second, |r| r.timestamp) };
+            let messages: Vec<&str> = merged.iter().map(|r| r.message.as_str()).collect();
+            assert_eq!(messages, vec!["a", "x", "b", "c"]);
+
+            assert_eq!(
+                unsafe { ranges::merge(&[1, 4], &vec![2, 3, 5], ranges::identity) },
+                vec![1, 2, 3, 4, 5]
+            );
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
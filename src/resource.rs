@@ -0,0 +1,545 @@
+//! Named thread pools, set up through `hpx::resource::partitioner`.
+//!
+//! By default HPX runs every worker thread in a single pool named
+//! `"default"`. A [`Partitioner`] creates more pools before the runtime
+//! starts, each with its own scheduler and its own cores or processing units
+//! (PUs), so that, say, network handling never waits behind compute work.
+//! The PUs not given to a pool stay in the default pool. Once the runtime
+//! runs, [`ParallelExecutor::on_pool`](crate::execution::ParallelExecutor::on_pool)
+//! submits work to a pool, and the [`SchedulerMode`] of a pool can be
+//! queried and changed:
+//!
+//! ```ignore
+//! let mut partitioner = Partitioner::new();
+//! partitioner
+//!     .create_thread_pool("network", SchedulingPolicy::LocalPriorityFifo)?
+//!     .add_pus("network", [0])?;
+//! let result = unsafe { partitioner.init(hpx_main, argc, argv.as_mut_ptr())? };
+//!
+//! // In hpx_main:
+//! let network = ParallelExecutor::on_pool("network")?;
+//! ```
+
+use hpx_sys::ffi;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+use std::os::raw::c_char;
+
+/// The scheduler of a thread pool, `hpx::resource::scheduling_policy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum SchedulingPolicy {
+    /// One queue per worker, stealing from the other workers.
+    Local = 0,
+    /// Like `Local`, with separate queues for high priority threads, run
+    /// first in, first out. The HPX default.
+    LocalPriorityFifo = 1,
+    /// Like `LocalPriorityFifo`, run last in, first out.
+    LocalPriorityLifo = 2,
+    /// One queue per worker, without stealing.
+    Static = 3,
+    /// Like `Static`, with separate queues for high priority threads.
+    StaticPriority = 4,
+    /// Priority queues stolen from at the other end, first in, first out.
+    AbpPriorityFifo = 5,
+    /// Like `AbpPriorityFifo`, last in, first out.
+    AbpPriorityLifo = 6,
+    /// Priority queues shared by the workers of each NUMA domain.
+    SharedPriority = 7,
+}
+
+impl SchedulingPolicy {
+    /// Returns the name HPX gives the policy in the `--hpx:queuing` option
+    /// and the `hpx.scheduler` configuration entry, which select the
+    /// scheduler of the default pool without a [`Partitioner`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchedulingPolicy::Local => "local",
+            SchedulingPolicy::LocalPriorityFifo => "local-priority-fifo",
+            SchedulingPolicy::LocalPriorityLifo => "local-priority-lifo",
+            SchedulingPolicy::Static => "static",
+            SchedulingPolicy::StaticPriority => "static-priority",
+            SchedulingPolicy::AbpPriorityFifo => "abp-priority-fifo",
+            SchedulingPolicy::AbpPriorityLifo => "abp-priority-lifo",
+            SchedulingPolicy::SharedPriority => "shared-priority",
+        }
+    }
+
+    fn from_raw(policy: i8) -> Option<Self> {
+        use SchedulingPolicy::*;
+        [
+            Local,
+            LocalPriorityFifo,
+            LocalPriorityLifo,
+            Static,
+            StaticPriority,
+            AbpPriorityFifo,
+            AbpPriorityLifo,
+            SharedPriority,
+        ]
+        .into_iter()
+        .find(|candidate| *candidate as i8 == policy)
+    }
+}
+
+/// Flags tuning a scheduler, `hpx::threads::policies::scheduler_mode`.
+///
+/// Combine them with `|` and test them with [`SchedulerMode::contains`]:
+///
+/// ```ignore
+/// let mode = SchedulerMode::DEFAULT | SchedulerMode::ENABLE_IDLE_BACKOFF;
+/// assert!(mode.contains(SchedulerMode::ENABLE_STEALING));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SchedulerMode(u32);
+
+impl SchedulerMode {
+    /// No flags set.
+    pub const NOTHING_SPECIAL: Self = SchedulerMode(0x000);
+    /// Run background work, like parcel handling, on idle workers.
+    pub const DO_BACKGROUND_WORK: Self = SchedulerMode(0x001);
+    /// Run background work at reduced priority.
+    pub const REDUCE_THREAD_PRIORITY: Self = SchedulerMode(0x002);
+    /// Keep the workers alive a little after the last work ran.
+    pub const DELAY_EXIT: Self = SchedulerMode(0x004);
+    /// Poll less for new work when idle.
+    pub const FAST_IDLE_MODE: Self = SchedulerMode(0x008);
+    /// Allow workers to be suspended and resumed.
+    pub const ENABLE_ELASTICITY: Self = SchedulerMode(0x010);
+    /// Back off exponentially while idle instead of spinning.
+    pub const ENABLE_IDLE_BACKOFF: Self = SchedulerMode(0x020);
+    /// Steal work from other workers of the same NUMA domain.
+    pub const ENABLE_STEALING: Self = SchedulerMode(0x040);
+    /// Steal work from workers of other NUMA domains too.
+    pub const ENABLE_STEALING_NUMA: Self = SchedulerMode(0x080);
+    /// Spread new work over the workers round robin.
+    pub const ASSIGN_WORK_ROUND_ROBIN: Self = SchedulerMode(0x100);
+    /// Queue new work on the worker of the thread creating it.
+    pub const ASSIGN_WORK_THREAD_PARENT: Self = SchedulerMode(0x200);
+    /// Steal high priority work before normal work.
+    pub const STEAL_HIGH_PRIORITY_FIRST: Self = SchedulerMode(0x400);
+    /// Steal only after the local queues are empty.
+    pub const STEAL_AFTER_LOCAL: Self = SchedulerMode(0x800);
+    /// The flags HPX sets by default.
+    pub const DEFAULT: Self = SchedulerMode(
+        Self::DO_BACKGROUND_WORK.0
+            | Self::REDUCE_THREAD_PRIORITY.0
+            | Self::DELAY_EXIT.0
+            | Self::ENABLE_STEALING.0
+            | Self::ENABLE_STEALING_NUMA.0
+            | Self::ASSIGN_WORK_ROUND_ROBIN.0
+            | Self::STEAL_AFTER_LOCAL.0,
+    );
+    /// All flags set.
+    pub const ALL_FLAGS: Self = SchedulerMode(0xFFF);
+
+    /// Returns the flags as their `scheduler_mode` value.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if all flags of `other` are set in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for SchedulerMode {
+    fn default() -> Self {
+        SchedulerMode::DEFAULT
+    }
+}
+
+impl BitOr for SchedulerMode {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        SchedulerMode(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for SchedulerMode {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for SchedulerMode {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        SchedulerMode(self.0 & rhs.0)
+    }
+}
+
+impl Not for SchedulerMode {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        SchedulerMode(!self.0 & Self::ALL_FLAGS.0)
+    }
+}
+
+// A pool as described to the partitioner, before its cores are resolved to
+// PUs.
+struct Pool {
+    name: String,
+    policy: SchedulingPolicy,
+    mode: SchedulerMode,
+    cores: Vec<usize>,
+    pus: Vec<usize>,
+}
+
+/// Describes the thread pools to create when the runtime starts.
+#[derive(Default)]
+pub struct Partitioner {
+    pools: Vec<Pool>,
+}
+
+impl Partitioner {
+    /// Creates a partitioner that leaves all PUs in the default pool.
+    pub fn new() -> Self {
+        Partitioner::default()
+    }
+
+    /// Adds a pool named `name` scheduled by `policy` with the default
+    /// [`SchedulerMode`]. Naming it `"default"` changes the scheduler of the
+    /// default pool.
+    pub fn create_thread_pool(
+        &mut self,
+        name: &str,
+        policy: SchedulingPolicy,
+    ) -> Result<&mut Self, &'static str> {
+        self.create_thread_pool_with_mode(name, policy, SchedulerMode::DEFAULT)
+    }
+
+    /// Like [`create_thread_pool`](Self::create_thread_pool), with the flags
+    /// `mode` for the scheduler.
+    pub fn create_thread_pool_with_mode(
+        &mut self,
+        name: &str,
+        policy: SchedulingPolicy,
+        mode: SchedulerMode,
+    ) -> Result<&mut Self, &'static str> {
+        if self.pools.iter().any(|pool| pool.name == name) {
+            return Err("a thread pool with this name already exists");
+        }
+        self.pools.push(Pool {
+            name: name.to_string(),
+            policy,
+            mode,
+            cores: Vec::new(),
+            pus: Vec::new(),
+        });
+        Ok(self)
+    }
+
+    /// Gives all PUs of the cores with ids `cores` to the pool `name`.
+    pub fn add_cores(
+        &mut self,
+        name: &str,
+        cores: impl IntoIterator<Item = usize>,
+    ) -> Result<&mut Self, &'static str> {
+        let cores: Vec<usize> = cores.into_iter().collect();
+        if self
+            .pools
+            .iter()
+            .any(|pool| cores.iter().any(|id| pool.cores.contains(id)))
+        {
+            return Err("a core is already assigned to a thread pool");
+        }
+        self.pool_mut(name)?.cores.extend(cores);
+        Ok(self)
+    }
+
+    /// Gives the PUs with ids `pus` to the pool `name`.
+    pub fn add_pus(
+        &mut self,
+        name: &str,
+        pus: impl IntoIterator<Item = usize>,
+    ) -> Result<&mut Self, &'static str> {
+        let pus: Vec<usize> = pus.into_iter().collect();
+        if self
+            .pools
+            .iter()
+            .any(|pool| pus.iter().any(|id| pool.pus.contains(id)))
+        {
+            return Err("a PU is already assigned to a thread pool");
+        }
+        self.pool_mut(name)?.pus.extend(pus);
+        Ok(self)
+    }
+
+    fn pool_mut(&mut self, name: &str) -> Result<&mut Pool, &'static str> {
+        self.pools
+            .iter_mut()
+            .find(|pool| pool.name == name)
+            .ok_or("no thread pool with this name")
+    }
+
+    // Resolves the cores of every pool to their PUs on `topology`, checking
+    // that every core and PU exists, that no PU ends up in two pools, and
+    // that the default pool keeps at least one PU.
+    fn resolve(&self, topology: &ffi::Topology) -> Result<Vec<ffi::PoolSpec>, &'static str> {
+        let mut owners: Vec<Option<usize>> = vec![None; topology.pus];
+        for (index, pool) in self.pools.iter().enumerate() {
+            if pool.cores.iter().any(|&core| core >= topology.cores)
+                || pool.pus.iter().any(|&pu| pu >= topology.pus)
+            {
+                return Err("a core or PU does not exist on this machine");
+            }
+            let core_pus =
+                (0..topology.pus).filter(|&pu| pool.cores.contains(&topology.pu_cores[pu]));
+            for pu in core_pus.chain(pool.pus.iter().copied()) {
+                match owners[pu] {
+                    Some(owner) if owner != index => {
+                        return Err("a PU is assigned to more than one thread pool")
+                    }
+                    _ => owners[pu] = Some(index),
+                }
+            }
+        }
+        let specs: Vec<ffi::PoolSpec> = self
+            .pools
+            .iter()
+            .enumerate()
+            .map(|(index, pool)| ffi::PoolSpec {
+                name: pool.name.clone(),
+                scheduler: pool.policy as i8,
+                mode: pool.mode.bits(),
+                pus: (0..topology.pus)
+                    .filter(|&pu| owners[pu] == Some(index))
+                    .collect(),
+            })
+            .collect();
+        if specs
+            .iter()
+            .any(|pool| pool.name != "default" && pool.pus.is_empty())
+        {
+            return Err("a thread pool has no cores or PUs");
+        }
+        let default_pus = match specs.iter().find(|pool| pool.name == "default") {
+            Some(pool) => !pool.pus.is_empty() || owners.contains(&None),
+            None => owners.contains(&None),
+        };
+        if !default_pus {
+            return Err("no PU is left for the default thread pool");
+        }
+        Ok(specs)
+    }
+
+    /// Starts the runtime with the pools, like [`ffi::init`], and returns the
+    /// exit code of `hpx_main`. Fails without starting the runtime if a pool
+    /// other than `"default"` has no cores or PUs, if a core or PU does not
+    /// exist, if two pools share a PU (also through one of its cores) or if
+    /// no PU is left for the default pool.
+    ///
+    /// # Safety
+    ///
+    /// `argv` must point to `argc` valid C strings, as for [`ffi::init`].
+    pub unsafe fn init(
+        &self,
+        hpx_main: fn(i32, *mut *mut c_char) -> i32,
+        argc: i32,
+        argv: *mut *mut c_char,
+    ) -> Result<i32, &'static str> {
+        let pools = self.resolve(&hpx_sys::topology())?;
+        ffi::init_with_pools(hpx_main, argc, argv, &pools)
+            .map_err(|_| "failed to create the thread pools")
+    }
+}
+
+/// Returns `true` if the runtime has a thread pool named `name`.
+pub fn pool_exists(name: &str) -> bool {
+    ffi::hpx_pool_exists(name)
+}
+
+/// Returns the number of thread pools of the runtime.
+pub fn get_num_thread_pools() -> usize {
+    ffi::hpx_get_num_thread_pools()
+}
+
+/// Returns the number of worker threads of the pool `name`, or `None` if
+/// there is no such pool.
+pub fn get_num_threads(name: &str) -> Option<usize> {
+    ffi::hpx_get_pool_num_threads(name).ok()
+}
+
+/// Returns the scheduler of the pool `name`, or `None` if there is no such
+/// pool.
+pub fn get_scheduling_policy(name: &str) -> Option<SchedulingPolicy> {
+    ffi::hpx_get_pool_scheduling_policy(name)
+        .ok()
+        .and_then(SchedulingPolicy::from_raw)
+}
+
+/// Returns the flags of the scheduler of the pool `name`, or `None` if there
+/// is no such pool.
+pub fn get_scheduler_mode(name: &str) -> Option<SchedulerMode> {
+    ffi::hpx_get_pool_scheduler_mode(name)
+        .ok()
+        .map(SchedulerMode)
+}
+
+/// Sets the flags `mode` on the scheduler of the running pool `name`.
+pub fn add_scheduler_mode(name: &str, mode: SchedulerMode) -> Result<(), &'static str> {
+    ffi::hpx_add_pool_scheduler_mode(name, mode.bits()).map_err(|_| "no thread pool with this name")
+}
+
+/// Clears the flags `mode` on the scheduler of the running pool `name`.
+pub fn remove_scheduler_mode(name: &str, mode: SchedulerMode) -> Result<(), &'static str> {
+    ffi::hpx_remove_pool_scheduler_mode(name, mode.bits())
+        .map_err(|_| "no thread pool with this name")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::execution::{Executor, ParallelExecutor};
+    use crate::resource::{self, Partitioner, SchedulerMode, SchedulingPolicy};
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+
+    #[test]
+    #[serial]
+    fn test_resource_partitioner() {
+        // "network" gets a PU of core 0 and "compute" gets core 1, which
+        // leaves "default" needing at least a third core.
+        let topology = hpx_sys::topology();
+        if topology.cores < 3 {
+            return;
+        }
+        let network_pu = topology
+            .pu_cores
+            .iter()
+            .position(|&core| core == 0)
+            .unwrap();
+
+        let mut partitioner = Partitioner::new();
+        partitioner
+            .create_thread_pool("network", SchedulingPolicy::LocalPriorityFifo)
+            .unwrap()
+            .add_pus("network", [network_pu])
+            .unwrap()
+            .create_thread_pool_with_mode(
+                "compute",
+                SchedulingPolicy::Static,
+                SchedulerMode::ENABLE_IDLE_BACKOFF,
+            )
+            .unwrap()
+            .add_cores("compute", 1..2)
+            .unwrap();
+        assert!(partitioner.add_pus("network", [network_pu]).is_err());
+        assert!(partitioner.add_pus("missing", [3]).is_err());
+        assert!(partitioner
+            .create_thread_pool("network", SchedulingPolicy::Local)
+            .is_err());
+        assert_eq!(SchedulingPolicy::StaticPriority.as_str(), "static-priority");
+
+        let (argc, mut argv) = create_c_args(&["test_resource_partitioner"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            assert_eq!(resource::get_num_thread_pools(), 3);
+            assert!(resource::pool_exists("default"));
+            assert!(resource::pool_exists("network"));
+            assert!(!resource::pool_exists("missing"));
+            assert_eq!(resource::get_num_threads("network"), Some(1));
+            assert_eq!(resource::get_num_threads("compute"), Some(1));
+            assert!(resource::get_num_threads("default").unwrap() >= 1);
+            assert_eq!(resource::get_num_threads("missing"), None);
+
+            let network = ParallelExecutor::on_pool("network").unwrap();
+            assert_eq!(network.pool(), Some("network"));
+            assert_eq!(network.async_execute(|| 1 + 1).get().unwrap(), 2);
+            assert!(ParallelExecutor::on_pool("missing").is_err());
+            assert_eq!(ParallelExecutor::new().pool(), None);
+
+            assert_eq!(
+                resource::get_scheduling_policy("network"),
+                Some(SchedulingPolicy::LocalPriorityFifo)
+            );
+            assert_eq!(
+                resource::get_scheduling_policy("compute"),
+                Some(SchedulingPolicy::Static)
+            );
+            assert_eq!(resource::get_scheduling_policy("missing"), None);
+
+            assert_eq!(
+                resource::get_scheduler_mode("network"),
+                Some(SchedulerMode::DEFAULT)
+            );
+            assert_eq!(
+                resource::get_scheduler_mode("compute"),
+                Some(SchedulerMode::ENABLE_IDLE_BACKOFF)
+            );
+            resource::add_scheduler_mode("network", SchedulerMode::ENABLE_IDLE_BACKOFF).unwrap();
+            resource::remove_scheduler_mode("network", SchedulerMode::ENABLE_STEALING).unwrap();
+            let mode = resource::get_scheduler_mode("network").unwrap();
+            assert!(mode.contains(SchedulerMode::ENABLE_IDLE_BACKOFF));
+            assert!(!mode.contains(SchedulerMode::ENABLE_STEALING));
+            assert_eq!(
+                mode & SchedulerMode::ENABLE_STEALING,
+                SchedulerMode::NOTHING_SPECIAL
+            );
+            assert!(resource::add_scheduler_mode("missing", SchedulerMode::DEFAULT).is_err());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = partitioner.init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, Ok(0));
+        }
+
+        let mut partitioner = Partitioner::new();
+        partitioner
+            .create_thread_pool("empty", SchedulingPolicy::Local)
+            .unwrap();
+        unsafe {
+            let result = partitioner.init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, Err("a thread pool has no cores or PUs"));
+        }
+
+        let pus = hpx_sys::topology().pus;
+        let mut partitioner = Partitioner::new();
+        partitioner
+            .create_thread_pool("missing", SchedulingPolicy::Local)
+            .unwrap()
+            .add_pus("missing", [pus])
+            .unwrap();
+        unsafe {
+            let result = partitioner.init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, Err("a core or PU does not exist on this machine"));
+        }
+
+        let core_zero_pu = hpx_sys::topology()
+            .pu_cores
+            .iter()
+            .position(|&core| core == 0)
+            .unwrap();
+        let mut partitioner = Partitioner::new();
+        partitioner
+            .create_thread_pool("cores", SchedulingPolicy::Local)
+            .unwrap()
+            .add_cores("cores", [0])
+            .unwrap()
+            .create_thread_pool("pus", SchedulingPolicy::Local)
+            .unwrap()
+            .add_pus("pus", [core_zero_pu])
+            .unwrap();
+        unsafe {
+            let result = partitioner.init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, Err("a PU is assigned to more than one thread pool"));
+        }
+
+        let mut partitioner = Partitioner::new();
+        partitioner
+            .create_thread_pool("all", SchedulingPolicy::Local)
+            .unwrap()
+            .add_pus("all", 0..pus)
+            .unwrap();
+        unsafe {
+            let result = partitioner.init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, Err("no PU is left for the default thread pool"));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Cooperative cancellation of HPX threads and parallel loops.
+//!
+//! A [`StopSource`] requests a stop and hands out [`StopToken`]s that observe
+//! it. The work being cancelled polls its token, or registers a
+//! [`StopCallback`] to be told when the stop is requested. A thread started
+//! with [`thread::spawn_jthread`](crate::thread::spawn_jthread) receives a
+//! token of its own, and [`for_loop`] stops scheduling new chunks of a
+//! parallel loop once its token is stopped:
+//!
+//! ```ignore
+//! let source = StopSource::new();
+//! let token = source.get_token();
+//! // On disconnect: source.request_stop();
+//! let completed = stop_token::for_loop(par, 0..candidates.len(), &token, |i| search(&candidates[i]));
+//! ```
+
+use crate::execution::{Executor, ParallelPolicy};
+use crate::this_thread;
+use cxx::UniquePtr;
+use hpx_sys::{ffi, ThreadTask};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The receiving end of a stop request, wrapping `hpx::stop_token`.
+///
+/// Clones observe the same stop state.
+pub struct StopToken(UniquePtr<ffi::stop_token>);
+
+impl StopToken {
+    pub(crate) fn new(token: UniquePtr<ffi::stop_token>) -> Self {
+        StopToken(token)
+    }
+
+    /// Returns `true` once a stop has been requested.
+    pub fn stop_requested(&self) -> bool {
+        ffi::hpx_stop_token_stop_requested(&self.0)
+    }
+
+    /// Returns `true` if a stop has been requested or may still be requested.
+    pub fn stop_possible(&self) -> bool {
+        ffi::hpx_stop_token_stop_possible(&self.0)
+    }
+}
+
+impl Clone for StopToken {
+    fn clone(&self) -> Self {
+        StopToken(ffi::hpx_stop_token_clone(&self.0))
+    }
+}
+
+/// The requesting end of a stop, wrapping `hpx::stop_source`.
+///
+/// Clones share the same stop state, so any of them may request the stop.
+pub struct StopSource(UniquePtr<ffi::stop_source>);
+
+impl StopSource {
+    /// Creates a source with a new stop state.
+    pub fn new() -> Self {
+        StopSource(ffi::hpx_stop_source_new())
+    }
+
+    /// Returns a token observing the stop state of this source.
+    pub fn get_token(&self) -> StopToken {
+        StopToken(ffi::hpx_stop_source_get_token(&self.0))
+    }
+
+    /// Requests a stop and runs the registered callbacks on the calling
+    /// thread. Returns `true` if this call made the request, `false` if a stop
+    /// had already been requested.
+    pub fn request_stop(&self) -> bool {
+        ffi::hpx_stop_source_request_stop(&self.0)
+    }
+
+    /// Returns `true` once a stop has been requested.
+    pub fn stop_requested(&self) -> bool {
+        ffi::hpx_stop_source_stop_requested(&self.0)
+    }
+
+    /// Returns `true` if the source has a stop state.
+    pub fn stop_possible(&self) -> bool {
+        ffi::hpx_stop_source_stop_possible(&self.0)
+    }
+}
+
+impl Default for StopSource {
+    fn default() -> Self {
+        StopSource::new()
+    }
+}
+
+impl Clone for StopSource {
+    fn clone(&self) -> Self {
+        StopSource(ffi::hpx_stop_source_clone(&self.0))
+    }
+}
+
+/// A closure run when a stop is requested on a token, wrapping
+/// `hpx::stop_callback`.
+///
+/// Dropping it before the request unregisters the closure.
+pub struct StopCallback {
+    // Only held to unregister the closure on drop.
+    _callback: UniquePtr<ffi::hpx_stop_callback>,
+}
+
+impl StopCallback {
+    /// Registers `f` to run on the thread calling
+    /// [`StopSource::request_stop`]. If a stop has already been requested,
+    /// `f` runs immediately on the calling thread. A panic in `f` aborts the
+    /// process.
+    pub fn new<F>(token: &StopToken, f: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        StopCallback {
+            _callback: ffi::hpx_stop_callback_new(&token.0, ThreadTask::new(move |_| f())),
+        }
+    }
+}
+
+/// Calls `f` on every index of `range` under `policy`, unless `token` is
+/// stopped, like [`execution::for_loop`](crate::execution::for_loop).
+///
+/// The range is split into chunks, and the token is checked before each
+/// chunk starts: once a stop is requested, no new chunk is run while those
+/// already running finish. Returns `true` if every index was visited.
+pub fn for_loop<E: Executor>(
+    policy: ParallelPolicy<E>,
+    range: Range<usize>,
+    token: &StopToken,
+    f: impl Fn(usize) + Sync,
+) -> bool {
+    let len = range.len();
+    // Four chunks per worker, as HPX's default chunk size does. There are no
+    // workers outside the runtime.
+    let chunks = (4 * this_thread::get_num_worker_threads()).max(1);
+    let chunk_size = len.div_ceil(chunks).max(1);
+    let stopped = AtomicBool::new(false);
+    let chunk = |chunk: usize| {
+        if token.stop_requested() {
+            stopped.store(true, Ordering::Relaxed);
+            return;
+        }
+        let start = range.start + chunk * chunk_size;
+        (start..(start + chunk_size).min(range.end)).for_each(&f);
+    };
+    policy.for_each_index(len.div_ceil(chunk_size), &chunk);
+    !stopped.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::execution::{par, ParallelExecutor};
+    use crate::stop_token::{self, StopCallback, StopSource};
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    #[test]
+    #[serial]
+    fn test_stop_source_callback() {
+        let (argc, mut argv) = create_c_args(&["test_stop_source_callback"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let source = StopSource::new();
+            let token = source.get_token();
+            let token_clone = token.clone();
+            assert!(source.stop_possible() && token.stop_possible());
+            assert!(!token.stop_requested());
+
+            let (sender, receiver) = mpsc::channel();
+            let sender_clone = sender.clone();
+            let _callback = StopCallback::new(&token, move || sender.send("first").unwrap());
+            let dropped = StopCallback::new(&token, move || sender_clone.send("dropped").unwrap());
+            drop(dropped);
+
+            assert!(source.clone().request_stop());
+            assert!(!source.request_stop());
+            assert!(source.stop_requested());
+            assert!(token.stop_requested() && token_clone.stop_requested());
+            assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec!["first"]);
+
+            // A callback registered after the request runs immediately.
+            let (sender, receiver) = mpsc::channel();
+            let _late = StopCallback::new(&token, move || sender.send(()).unwrap());
+            assert!(receiver.try_recv().is_ok());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_stop_token_for_loop() {
+        let (argc, mut argv) = create_c_args(&["test_stop_token_for_loop"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let source = StopSource::new();
+            let token = source.get_token();
+
+            let sum = AtomicUsize::new(0);
+            assert!(stop_token::for_loop(par, 10..1010, &token, |i| {
+                sum.fetch_add(i, Ordering::Relaxed);
+            }));
+            assert_eq!(sum.into_inner(), (10..1010).sum::<usize>());
+            assert!(stop_token::for_loop(par, 5..5, &token, |_| unreachable!()));
+
+            let exec = ParallelExecutor::new();
+            let visited = AtomicUsize::new(0);
+            let len = 100_000;
+            let completed = stop_token::for_loop(par.on(&exec), 0..len, &token, |i| {
+                visited.fetch_add(1, Ordering::Relaxed);
+                if i == 0 {
+                    source.request_stop();
+                }
+            });
+            assert!(!completed);
+            assert!(visited.load(Ordering::Relaxed) < len);
+
+            assert!(!stop_token::for_loop(
+                par,
+                0..10,
+                &token,
+                |_| unreachable!()
+            ));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Cooperation with the HPX scheduler from the running HPX thread.
+//!
+//! An HPX thread is a lightweight task multiplexed onto the OS worker threads
+//! of the runtime. Blocking it with `std::thread::sleep` stalls the whole
+//! worker, while [`sleep_for`] and [`yield_now`] suspend only the HPX thread
+//! and let the worker run other tasks meanwhile.
+
+use hpx_sys::ffi;
+use std::num::NonZeroU64;
+use std::time::{Duration, Instant};
+
+/// Identifier of an HPX thread, unique among the threads alive at the same
+/// time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ThreadId(NonZeroU64);
+
+impl ThreadId {
+    // The bindings report "no thread" as id 0.
+    pub(crate) fn from_raw(id: u64) -> Option<Self> {
+        NonZeroU64::new(id).map(ThreadId)
+    }
+}
+
+/// Suspends the current HPX thread and lets the scheduler run other work
+/// before resuming it.
+pub fn yield_now() {
+    ffi::hpx_this_thread_yield();
+}
+
+/// Suspends the current HPX thread for at least `duration`.
+pub fn sleep_for(duration: Duration) {
+    let nanoseconds = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+    ffi::hpx_this_thread_sleep_for(nanoseconds);
+}
+
+/// Suspends the current HPX thread until `deadline`.
+///
+/// `Instant` cannot be converted to the C++ steady clock, so this sleeps for
+/// the time remaining until `deadline` instead of passing it to
+/// `hpx::this_thread::sleep_until`.
+pub fn sleep_until(deadline: Instant) {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if !remaining.is_zero() {
+        sleep_for(remaining);
+    }
+}
+
+/// Returns the id of the current HPX thread, or `None` when not called from
+/// an HPX thread.
+pub fn get_id() -> Option<ThreadId> {
+    ThreadId::from_raw(ffi::hpx_this_thread_get_id())
+}
+
+/// Returns the index of the OS worker thread running the caller, or `None`
+/// when not called from a worker thread of the runtime.
+pub fn get_worker_thread_num() -> Option<usize> {
+    match ffi::hpx_get_worker_thread_num() {
+        usize::MAX => None,
+        num => Some(num),
+    }
+}
+
+/// Returns the number of OS worker threads of the runtime on this locality.
+pub fn get_num_worker_threads() -> usize {
+    ffi::hpx_get_num_worker_threads() as usize
+}
+
+/// Returns the id of the locality the caller runs on, or `None` when the
+/// runtime is not running.
+pub fn get_locality_id() -> Option<u32> {
+    match ffi::hpx_get_locality_id() {
+        u32::MAX => None,
+        id => Some(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::this_thread;
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    #[serial]
+    fn test_this_thread_queries() {
+        assert_eq!(this_thread::get_id(), None);
+        assert_eq!(this_thread::get_worker_thread_num(), None);
+
+        let (argc, mut argv) = create_c_args(&["test_this_thread_queries"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let id = this_thread::get_id();
+            assert!(id.is_some());
+            assert_eq!(this_thread::get_id(), id);
+
+            let workers = this_thread::get_num_worker_threads();
+            assert!(workers >= 1);
+            assert!(this_thread::get_worker_thread_num().unwrap() < workers);
+            assert_eq!(this_thread::get_locality_id(), Some(0));
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_this_thread_sleep_yield() {
+        let (argc, mut argv) = create_c_args(&["test_this_thread_sleep_yield"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            this_thread::yield_now();
+
+            let start = Instant::now();
+            this_thread::sleep_for(Duration::from_millis(10));
+            assert!(start.elapsed() >= Duration::from_millis(10));
+
+            let deadline = Instant::now() + Duration::from_millis(5);
+            this_thread::sleep_until(deadline);
+            assert!(Instant::now() >= deadline);
+            this_thread::sleep_until(start);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}
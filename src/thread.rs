@@ -0,0 +1,295 @@
+//! Spawning HPX threads.
+//!
+//! The API mirrors `std::thread`: [`spawn`] starts a closure on a new HPX
+//! thread and returns a [`JoinHandle`] for its result, and [`post`] starts a
+//! closure without a handle. [`spawn_jthread`] starts a closure that receives
+//! a [`StopToken`] and is asked to stop when its [`JThread`] is dropped.
+//!
+//! A panic in the closure is caught on the HPX thread and returned by `join`,
+//! as with `std::thread`.
+//!
+//! [`ThreadPriority`], [`ThreadStacksize`] and [`ScheduleHint`] describe how
+//! HPX schedules a thread. They are set for all work submitted through an
+//! executor with
+//! [`ParallelExecutor::with_priority`](crate::execution::ParallelExecutor::with_priority)
+//! and its siblings.
+
+use crate::stop_token::StopToken;
+use crate::this_thread::ThreadId;
+use cxx::UniquePtr;
+use hpx_sys::{ffi, ThreadTask};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::thread::Result;
+
+/// The priority of an HPX thread, `hpx::threads::thread_priority`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum ThreadPriority {
+    /// The priority of the thread pool's scheduler, usually `Normal`.
+    #[default]
+    Default = 0,
+    /// Runs only when no other work is queued.
+    Low = 1,
+    Normal = 2,
+    /// Like `High`, and threads spawned from it inherit the priority.
+    HighRecursive = 3,
+    /// Runs the first time as `High`, then as `Normal`.
+    Boost = 4,
+    /// Queued ahead of `Normal` work.
+    High = 5,
+    /// Like `High`, and never stolen by another worker.
+    Bound = 6,
+}
+
+/// The stack size of an HPX thread, `hpx::threads::thread_stacksize`. The
+/// sizes are set by the runtime configuration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum ThreadStacksize {
+    #[default]
+    Small = 1,
+    Medium = 2,
+    Large = 3,
+    Huge = 4,
+    /// Runs the thread on the stack of the worker; it must not suspend.
+    Nostack = 5,
+}
+
+/// Where HPX should run a thread, `hpx::threads::thread_schedule_hint`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ScheduleHint {
+    /// Let the scheduler choose.
+    #[default]
+    None,
+    /// Run on the worker thread with this index.
+    Thread(i16),
+    /// Run on a worker thread of this NUMA domain.
+    Numa(i16),
+}
+
+impl ScheduleHint {
+    // The `hpx::threads::thread_schedule_hint_mode` and hint value.
+    pub(crate) fn to_raw(self) -> (i8, i16) {
+        match self {
+            ScheduleHint::None => (0, -1),
+            ScheduleHint::Thread(worker) => (1, worker),
+            ScheduleHint::Numa(domain) => (2, domain),
+        }
+    }
+}
+
+// Where the closure leaves its result for `join`.
+pub(crate) type Packet<T> = Arc<Mutex<Option<Result<T>>>>;
+
+pub(crate) fn run_into<T>(packet: &Packet<T>, f: impl FnOnce() -> T) {
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    *packet.lock().unwrap() = Some(result);
+}
+
+pub(crate) fn take_result<T>(packet: &Packet<T>) -> Result<T> {
+    packet
+        .lock()
+        .unwrap()
+        .take()
+        .expect("a joined thread has stored its result")
+}
+
+/// Runs `f` on a new HPX thread without waiting for it. A panic in `f` is
+/// discarded.
+pub fn post<F>(f: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    ffi::hpx_post(ThreadTask::new(move |_| {
+        let _ = panic::catch_unwind(AssertUnwindSafe(f));
+    }));
+}
+
+/// An owned permission to join an HPX thread started by [`spawn`].
+///
+/// Dropping the handle detaches the thread.
+pub struct JoinHandle<T> {
+    thread: UniquePtr<ffi::thread>,
+    packet: Packet<T>,
+    id: ThreadId,
+}
+
+/// Runs `f` on a new HPX thread and returns a handle to wait for its result.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let packet: Packet<T> = Arc::new(Mutex::new(None));
+    let thread_packet = Arc::clone(&packet);
+    let thread = ffi::hpx_thread_spawn(ThreadTask::new(move |_| run_into(&thread_packet, f)));
+    let id =
+        ThreadId::from_raw(ffi::hpx_thread_get_id(&thread)).expect("a running thread has an id");
+    JoinHandle { thread, packet, id }
+}
+
+impl<T> JoinHandle<T> {
+    /// Waits for the thread to finish and returns the value of its closure,
+    /// or the payload of its panic.
+    pub fn join(mut self) -> Result<T> {
+        let mut thread = std::mem::replace(&mut self.thread, UniquePtr::null());
+        ffi::hpx_thread_join(thread.pin_mut());
+        take_result(&self.packet)
+    }
+
+    /// Returns the id of the thread.
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+
+    /// Returns `true` once the closure has returned or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.packet.lock().unwrap().is_some()
+    }
+}
+
+impl<T> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.as_mut() {
+            ffi::hpx_thread_detach(thread);
+        }
+    }
+}
+
+/// An HPX thread started by [`spawn_jthread`], wrapping `hpx::jthread`.
+///
+/// Dropping it requests a stop and waits for the thread to finish, discarding
+/// its result.
+pub struct JThread<T> {
+    thread: UniquePtr<ffi::jthread>,
+    packet: Packet<T>,
+    id: ThreadId,
+}
+
+/// Runs `f` on a new HPX thread, passing it a token that reports when
+/// [`JThread::request_stop`] is called or the `JThread` is dropped.
+pub fn spawn_jthread<F, T>(f: F) -> JThread<T>
+where
+    F: FnOnce(StopToken) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let packet: Packet<T> = Arc::new(Mutex::new(None));
+    let thread_packet = Arc::clone(&packet);
+    let thread = ffi::hpx_jthread_spawn(ThreadTask::new(move |token| {
+        let token = StopToken::new(token.expect("a jthread passes its stop token"));
+        run_into(&thread_packet, || f(token))
+    }));
+    let id =
+        ThreadId::from_raw(ffi::hpx_jthread_get_id(&thread)).expect("a running thread has an id");
+    JThread { thread, packet, id }
+}
+
+impl<T> JThread<T> {
+    /// Asks the thread to stop. Returns `true` if this call made the request,
+    /// `false` if a stop had already been requested.
+    pub fn request_stop(&mut self) -> bool {
+        ffi::hpx_jthread_request_stop(self.thread.pin_mut())
+    }
+
+    /// Waits for the thread to finish, without requesting a stop, and returns
+    /// the value of its closure or the payload of its panic.
+    pub fn join(mut self) -> Result<T> {
+        ffi::hpx_jthread_join(self.thread.pin_mut());
+        take_result(&self.packet)
+    }
+
+    /// Returns the id of the thread.
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{this_thread, thread};
+    use hpx_sys::{create_c_args, ffi};
+    use serial_test::serial;
+    use std::os::raw::c_char;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    #[serial]
+    fn test_thread_spawn_join() {
+        let (argc, mut argv) = create_c_args(&["test_thread_spawn_join"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let data = [1, 2, 3];
+            let handle = thread::spawn(move || {
+                assert!(this_thread::get_id().is_some());
+                (this_thread::get_id().unwrap(), data.iter().sum::<i32>())
+            });
+            let id = handle.id();
+            assert_ne!(Some(id), this_thread::get_id());
+            assert_eq!(handle.join().unwrap(), (id, 6));
+
+            let handle = thread::spawn(|| -> i32 { panic!("expected panic") });
+            let payload = handle.join().unwrap_err();
+            assert_eq!(payload.downcast_ref::<&str>(), Some(&"expected panic"));
+
+            let (sender, receiver) = mpsc::channel();
+            thread::post(move || sender.send(42).unwrap());
+            assert_eq!(receiver.recv().unwrap(), 42);
+
+            // Dropping the handle detaches the thread.
+            let (sender, receiver) = mpsc::channel();
+            drop(thread::spawn(move || sender.send(7).unwrap()));
+            assert_eq!(receiver.recv().unwrap(), 7);
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_thread_jthread_stop() {
+        let (argc, mut argv) = create_c_args(&["test_thread_jthread_stop"]);
+
+        let hpx_main = |_argc: i32, _argv: *mut *mut c_char| -> i32 {
+            let mut worker = thread::spawn_jthread(|token| {
+                assert!(token.stop_possible());
+                let mut iterations = 0;
+                while !token.stop_requested() {
+                    iterations += 1;
+                    this_thread::sleep_for(Duration::from_millis(1));
+                }
+                iterations
+            });
+            this_thread::sleep_for(Duration::from_millis(5));
+            assert!(worker.request_stop());
+            assert!(!worker.request_stop());
+            assert!(worker.join().unwrap() >= 1);
+
+            let finished = thread::spawn_jthread(|token| token.stop_requested());
+            assert!(!finished.join().unwrap());
+
+            // Dropping the thread requests a stop and joins it.
+            let (sender, receiver) = mpsc::channel();
+            drop(thread::spawn_jthread(move |token| {
+                while !token.stop_requested() {
+                    this_thread::yield_now();
+                }
+                sender.send(()).unwrap();
+            }));
+            assert!(receiver.try_recv().is_ok());
+
+            ffi::finalize()
+        };
+
+        unsafe {
+            let result = ffi::init(hpx_main, argc, argv.as_mut_ptr());
+            assert_eq!(result, 0);
+        }
+    }
+}